@@ -23,13 +23,27 @@ impl<E> ContextualError<E> {
   pub fn new(error: E, context: String) -> Self {
     ContextualError { error, context }
   }
+
+  /// Transforms the context message, keeping the error as is.
+  pub fn map_context(self, f: impl FnOnce(String) -> String) -> Self {
+    ContextualError { context: f(self.context), error: self.error }
+  }
+
+  /// Prepends the given prefix to the context message.
+  pub fn with_prefix(self, prefix: &str) -> Self {
+    self.map_context(|context| format!("{prefix}{context}"))
+  }
 }
 
 impl<E> Display for ContextualError<E>
 where E: Display {
   #[inline]
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    write!(f, "{} ({})", self.context, self.error)
+    if self.context.is_empty() {
+      write!(f, "{}", self.error)
+    } else {
+      write!(f, "{} ({})", self.context, self.error)
+    }
   }
 }
 
@@ -47,6 +61,15 @@ impl<E> From<(E, String)> for ContextualError<E> {
   }
 }
 
+/// Wraps an error with an empty context, allowing `?` to be used on plain errors.
+/// Prefer [`Contextualize::context`] wherever a meaningful message can be given.
+impl<E> From<E> for ContextualError<E> {
+  #[inline]
+  fn from(error: E) -> Self {
+    ContextualError { error, context: String::new() }
+  }
+}
+
 
 
 /// An extension trait for [`Result<T, E>`][Result] that allows
@@ -78,6 +101,37 @@ impl<T, E> Contextualize for Result<T, E> {
   }
 }
 
+/// An extension trait for [`Result<T, E>`][Result] that allows
+/// the context to depend on the error being described.
+pub trait ContextualizeErr: Contextualize {
+  type Error;
+
+  /// Like [`Contextualize::context`], but builds the message from the error itself.
+  fn context_from(self, message_provider: impl FnOnce(&Self::Error) -> String) -> Self::Output;
+
+  /// Like [`Contextualize::context`], but only attaches the message if the error satisfies the predicate.
+  /// Otherwise the error is wrapped with an empty context, which displays as the bare error.
+  fn context_if(self, pred: impl FnOnce(&Self::Error) -> bool, message: impl Into<String>) -> Self::Output where Self: Sized {
+    self.context_from(|error| if pred(error) { message.into() } else { String::new() })
+  }
+
+  /// Runs a side effect on the error, if there is one, returning `self` unchanged.
+  fn inspect_context_err(self, f: impl FnOnce(&Self::Error)) -> Self;
+}
+
+impl<T, E> ContextualizeErr for Result<T, E> {
+  type Error = E;
+
+  fn context_from(self, message_provider: impl FnOnce(&E) -> String) -> Self::Output {
+    self.map_err(|error| ContextualError { context: (message_provider)(&error), error })
+  }
+
+  fn inspect_context_err(self, f: impl FnOnce(&E)) -> Self {
+    if let Err(error) = &self { f(error) };
+    self
+  }
+}
+
 macro_rules! maybe {
   ($value:expr, $error:pat, $out:expr) => {
     match $value {
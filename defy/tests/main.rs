@@ -0,0 +1,74 @@
+extern crate defy;
+
+use defy::{Contextualize, ContextualizeErr, ContextualError};
+
+use std::num::ParseIntError;
+
+#[test]
+fn context_from() {
+  let result = "12a".parse::<u32>()
+    .context_from(|error| format!("failed to parse: {error:?}"));
+  let ContextualError { error, context } = result.unwrap_err();
+  assert_eq!(context, format!("failed to parse: {error:?}"));
+
+  let result: Result<u32, ContextualError<ParseIntError>> = "12".parse::<u32>()
+    .context_from(|_| unreachable!());
+  assert_eq!(result, Ok(12));
+}
+
+#[test]
+fn question_mark() {
+  fn parse(s: &str) -> Result<u32, ContextualError<ParseIntError>> {
+    Ok(s.parse::<u32>()?)
+  }
+
+  assert_eq!(parse("7"), Ok(7));
+  let error = parse("x").unwrap_err();
+  assert!(error.context.is_empty());
+  assert_eq!(error.to_string(), error.error.to_string());
+
+  let error: ContextualError<ParseIntError> = ContextualError::from(("x".parse::<u32>().unwrap_err(), "parsing x".to_owned()));
+  assert_eq!(error.to_string(), format!("parsing x ({})", error.error));
+}
+
+#[test]
+fn context_if() {
+  use std::num::IntErrorKind;
+
+  fn parse(s: &str) -> Result<u8, ContextualError<ParseIntError>> {
+    s.parse::<u8>().context_if(|error| error.kind() == &IntErrorKind::PosOverflow, "value too large")
+  }
+
+  assert_eq!(parse("7"), Ok(7));
+  let error = parse("300").unwrap_err();
+  assert_eq!(error.context, "value too large");
+  let error = parse("x").unwrap_err();
+  assert!(error.context.is_empty());
+  assert_eq!(error.to_string(), error.error.to_string());
+}
+
+#[test]
+fn inspect_context_err() {
+  let mut seen = Vec::new();
+  let ok = "7".parse::<u32>().inspect_context_err(|error| seen.push(error.to_string()));
+  assert_eq!(ok, Ok(7));
+  assert!(seen.is_empty());
+
+  let err = "x".parse::<u32>().inspect_context_err(|error| seen.push(error.to_string()));
+  assert_eq!(err, "x".parse::<u32>());
+  assert_eq!(seen, [err.unwrap_err().to_string()]);
+}
+
+#[test]
+fn map_context() {
+  let error = "x".parse::<u32>().context("parsing port").unwrap_err();
+  let inner = error.error.clone();
+
+  let error = error.with_prefix("loading config: ");
+  assert_eq!(error.context, "loading config: parsing port");
+  assert_eq!(error.to_string(), format!("loading config: parsing port ({inner})"));
+
+  let error = error.map_context(|context| context.to_uppercase());
+  assert_eq!(error.context, "LOADING CONFIG: PARSING PORT");
+  assert_eq!(error.error, inner);
+}
@@ -0,0 +1,223 @@
+//! A compact binary encoding of graphs that does not depend on serde.
+//!
+//! All integers are written in little-endian byte order. A graph is written as its current ID,
+//! followed by a length-prefixed list of `(id, value)` nodes and a length-prefixed list
+//! of `(id, id, value)` links. Neighbor lists are rebuilt from the links when decoding.
+
+use ids::IdContext;
+
+use crate::{Graph, Id, NodeInner, UOrd};
+
+use std::error::Error;
+use std::fmt;
+
+
+
+/// A value that can be written with [`Graph::to_bytes`].
+pub trait Encode {
+  /// Appends the encoded form of this value to the given buffer.
+  fn encode(&self, buf: &mut Vec<u8>);
+}
+
+/// A value that can be read with [`Graph::from_bytes`].
+pub trait Decode: Sized {
+  /// Reads a value from the front of the given slice, advancing it past the bytes consumed.
+  fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError>;
+}
+
+/// An error encountered while decoding a graph with [`Graph::from_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+  /// The input ended before the value was fully read.
+  UnexpectedEnd,
+  /// The input contained bytes after the end of the graph.
+  TrailingBytes,
+  /// A value was malformed, such as a `bool` that was neither `0` nor `1`.
+  InvalidValue,
+  /// The same node appeared more than once.
+  DuplicateNode,
+  /// The same link appeared more than once.
+  DuplicateLink,
+  /// A link referred to a node that does not exist, or linked a node to itself.
+  InvalidLink
+}
+
+impl fmt::Display for DecodeError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str(match self {
+      DecodeError::UnexpectedEnd => "unexpected end of input",
+      DecodeError::TrailingBytes => "trailing bytes after graph",
+      DecodeError::InvalidValue => "invalid value",
+      DecodeError::DuplicateNode => "duplicate node",
+      DecodeError::DuplicateLink => "duplicate link",
+      DecodeError::InvalidLink => "invalid link"
+    })
+  }
+}
+
+impl Error for DecodeError {}
+
+fn take<const N: usize>(bytes: &mut &[u8]) -> Result<[u8; N], DecodeError> {
+  if bytes.len() < N { return Err(DecodeError::UnexpectedEnd) };
+  let (head, tail) = bytes.split_at(N);
+  *bytes = tail;
+  Ok(head.try_into().unwrap())
+}
+
+macro_rules! impl_encode_decode_num {
+  ($($Type:ty),* $(,)?) => {$(
+    impl Encode for $Type {
+      #[inline]
+      fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_le_bytes());
+      }
+    }
+
+    impl Decode for $Type {
+      #[inline]
+      fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        take(bytes).map(<$Type>::from_le_bytes)
+      }
+    }
+  )*};
+}
+
+impl_encode_decode_num!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl Encode for bool {
+  #[inline]
+  fn encode(&self, buf: &mut Vec<u8>) {
+    buf.push(*self as u8);
+  }
+}
+
+impl Decode for bool {
+  #[inline]
+  fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+    match u8::decode(bytes)? {
+      0 => Ok(false),
+      1 => Ok(true),
+      _ => Err(DecodeError::InvalidValue)
+    }
+  }
+}
+
+impl Encode for () {
+  #[inline]
+  fn encode(&self, _: &mut Vec<u8>) {}
+}
+
+impl Decode for () {
+  #[inline]
+  fn decode(_: &mut &[u8]) -> Result<Self, DecodeError> {
+    Ok(())
+  }
+}
+
+impl<T: Encode, const N: usize> Encode for [T; N] {
+  fn encode(&self, buf: &mut Vec<u8>) {
+    for value in self {
+      value.encode(buf);
+    };
+  }
+}
+
+impl<T: Decode, const N: usize> Decode for [T; N] {
+  fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+    let mut values = Vec::with_capacity(N);
+    for _ in 0..N {
+      values.push(T::decode(bytes)?);
+    };
+
+    Ok(values.try_into().ok().unwrap())
+  }
+}
+
+impl<F: ?Sized> Encode for Id<F> {
+  #[inline]
+  fn encode(&self, buf: &mut Vec<u8>) {
+    self.into_raw().encode(buf);
+  }
+}
+
+impl<F: ?Sized> Decode for Id<F> {
+  #[inline]
+  fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+    u64::decode(bytes).map(Id::from_raw)
+  }
+}
+
+fn decode_len(bytes: &mut &[u8]) -> Result<usize, DecodeError> {
+  usize::try_from(u64::decode(bytes)?).map_err(|_| DecodeError::InvalidValue)
+}
+
+
+
+impl<Node, Link> Graph<Node, Link> {
+  /// Encodes this graph into a compact binary form, preserving node IDs.
+  /// Nodes and links are written in ascending order, so equal graphs encode identically.
+  pub fn to_bytes(&self) -> Vec<u8>
+  where Node: Encode, Link: Encode {
+    let mut buf = Vec::new();
+    self.id_context.current_id().encode(&mut buf);
+
+    let mut nodes = self.nodes.iter().collect::<Vec<_>>();
+    nodes.sort_unstable_by_key(|&(&id, _)| id);
+    (nodes.len() as u64).encode(&mut buf);
+    for (id, node_inner) in nodes {
+      id.encode(&mut buf);
+      node_inner.value.encode(&mut buf);
+    };
+
+    let mut links = self.links.iter()
+      .map(|(&nodes, value)| (nodes.into_tuple(), value))
+      .collect::<Vec<_>>();
+    links.sort_unstable_by_key(|&(nodes, _)| nodes);
+    (links.len() as u64).encode(&mut buf);
+    for ((node1, node2), value) in links {
+      node1.encode(&mut buf);
+      node2.encode(&mut buf);
+      value.encode(&mut buf);
+    };
+
+    buf
+  }
+
+  /// Decodes a graph written by [`Graph::to_bytes`].
+  /// Fails if the input is truncated, has trailing bytes, or does not describe a valid graph.
+  pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, DecodeError>
+  where Node: Decode, Link: Decode {
+    let bytes = &mut bytes;
+    let current_id = u64::decode(bytes)?;
+
+    let mut graph = Graph::new();
+    let mut next_id = 0;
+    for _ in 0..decode_len(bytes)? {
+      let id = Id::<Node>::decode(bytes)?;
+      let value = Node::decode(bytes)?;
+      let node_inner = NodeInner { value, neighbors: Default::default() };
+      if graph.nodes.insert(id, node_inner).is_some() {
+        return Err(DecodeError::DuplicateNode);
+      };
+
+      next_id = next_id.max(id.into_raw().saturating_add(1));
+    };
+
+    for _ in 0..decode_len(bytes)? {
+      let node1 = Id::<Node>::decode(bytes)?;
+      let node2 = Id::<Node>::decode(bytes)?;
+      let value = Link::decode(bytes)?;
+      if node1 == node2 || !graph.contains_node(node1) || !graph.contains_node(node2) {
+        return Err(DecodeError::InvalidLink);
+      };
+
+      if graph.add_link(value, UOrd::new(node1, node2)).is_some() {
+        return Err(DecodeError::DuplicateLink);
+      };
+    };
+
+    if !bytes.is_empty() { return Err(DecodeError::TrailingBytes) };
+    graph.id_context = IdContext::with_current_id(current_id.max(next_id));
+    Ok(graph)
+  }
+}
@@ -3,20 +3,23 @@ extern crate uord;
 extern crate nohash_hasher;
 
 use ids::IdContext;
-use nohash_hasher::{IntMap, IntSet};
-#[doc(no_inline)] pub use ids::Id;
+use nohash_hasher::IntMap;
+#[doc(no_inline)] pub use ids::{Id, IdSet};
 #[doc(no_inline)] pub use uord::UOrd;
 
+pub mod bytes;
+pub mod transaction;
+
+pub use crate::bytes::{Decode, DecodeError, Encode};
+pub use crate::transaction::Transaction;
 use crate::iter::*;
 
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use std::iter::Extend;
+use std::ops::Add;
 use std::fmt;
 
-pub type IdSet<F> = IntSet<Id<F>>;
-
-
-
 #[derive(Clone)]
 pub struct Graph<Node, Link> {
   id_context: IdContext<Node>,
@@ -33,11 +36,35 @@ impl<Node, Link> Graph<Node, Link> {
     }
   }
 
+  /// Creates an empty graph with space for at least the given number of nodes and links.
+  pub fn with_capacity(nodes: usize, links: usize) -> Self {
+    Graph {
+      id_context: IdContext::new(),
+      nodes: IntMap::with_capacity_and_hasher(nodes, Default::default()),
+      links: HashMap::with_capacity(links)
+    }
+  }
+
+  /// Creates a graph from a list of links between node IDs, with default node and link values.
+  /// The given IDs are preserved, see [`Graph::to_edge_list`].
+  /// Panics if any node is linked to itself.
+  pub fn from_edge_list(edges: impl IntoIterator<Item = (Id<Node>, Id<Node>)>) -> Self
+  where Node: Default, Link: Default {
+    let mut graph = Graph::new();
+    for (node1, node2) in edges {
+      graph.get_node_value_or_insert_with(node1, Node::default);
+      graph.get_node_value_or_insert_with(node2, Node::default);
+      graph.add_link(Link::default(), (node1, node2));
+    };
+
+    graph
+  }
+
   /// Adds a new node (with no links) to the graph, placing the given value inside it.
   pub fn add_node(&mut self, value: Node) -> Id<Node> {
     let id = self.id_context.next_id();
     self.nodes.insert(id, NodeInner {
-      value, neighbors: IntSet::default()
+      value, neighbors: IdSet::default()
     });
 
     id
@@ -72,6 +99,61 @@ impl<Node, Link> Graph<Node, Link> {
     })
   }
 
+  /// Collapses a link, merging its two nodes into one.
+  /// The lesser of the two node IDs survives and receives the value produced by `combine`,
+  /// which is given the lesser node's value first. Links from the other node are moved to the
+  /// survivor, and where both nodes were linked to the same neighbor only the survivor's link is kept.
+  /// Returns `None` if the link does not exist.
+  pub fn contract_link(
+    &mut self,
+    nodes: impl Into<UOrd<Id<Node>>>,
+    combine: impl FnOnce(Node, Node) -> Node
+  ) -> Option<Id<Node>> {
+    let nodes = nodes.into();
+    self.remove_link(nodes)?;
+    let (survivor, absorbed) = nodes.into_tuple();
+
+    let absorbed_inner = self.nodes.remove(&absorbed).unwrap();
+    for neighbor in absorbed_inner.neighbors {
+      let link = self.links.remove(&UOrd::new(absorbed, neighbor)).unwrap();
+      self.unlink_node_neighbor(neighbor, absorbed);
+      if !self.contains_link((survivor, neighbor)) {
+        self.add_link(link, (survivor, neighbor));
+      };
+    };
+
+    let mut survivor_inner = self.nodes.remove(&survivor).unwrap();
+    survivor_inner.value = combine(survivor_inner.value, absorbed_inner.value);
+    self.nodes.insert(survivor, survivor_inner);
+    Some(survivor)
+  }
+
+  /// Moves a node (and all of its links) to a new ID.
+  /// Returns `false` without modifying the graph if `old` does not exist or `new` is already taken.
+  pub fn reassign_node_id(&mut self, old: Id<Node>, new: Id<Node>) -> bool {
+    if !self.contains_node(old) || self.contains_node(new) { return false };
+
+    let node_inner = self.nodes.remove(&old).unwrap();
+    for neighbor in node_inner.neighbors.iter() {
+      let link = self.links.remove(&UOrd::new(old, neighbor)).unwrap();
+      self.links.insert(UOrd::new(new, neighbor), link);
+      let neighbors = self.get_node_neighbors_mut(neighbor).unwrap();
+      neighbors.remove(old);
+      neighbors.insert(new);
+    };
+
+    self.nodes.insert(new, node_inner);
+    self.reserve_id(new);
+    true
+  }
+
+  /// Ensures that the `IdContext` will never spawn the given ID in the future.
+  /// Reserving the maximum ID leaves the context exhausted, so `add_node` will panic afterwards.
+  fn reserve_id(&mut self, id: Id<Node>) {
+    let current_id = self.id_context.current_id().max(id.into_raw().saturating_add(1));
+    self.id_context = IdContext::with_current_id(current_id);
+  }
+
   /// Tries to remove a link from the graph.
   pub fn remove_link(&mut self, nodes: impl Into<UOrd<Id<Node>>>) -> Option<Link> {
     let nodes = nodes.into();
@@ -81,6 +163,12 @@ impl<Node, Link> Graph<Node, Link> {
     })
   }
 
+  /// Begins a batch of edits that can be reverted as a group, see [`Transaction`].
+  #[inline]
+  pub fn transaction(&mut self) -> Transaction<'_, Node, Link> {
+    Transaction::new(self)
+  }
+
   /// Returns the number of nodes that a given node is linked to.
   /// Returns `None` if no node exists for the given `Id`.
   pub fn node_neighbors_count(&self, id: Id<Node>) -> Option<usize> {
@@ -93,6 +181,401 @@ impl<Node, Link> Graph<Node, Link> {
     self.nodes.get(&id).map(|inner_node| &inner_node.neighbors)
   }
 
+  /// Returns an iterator over every link with at least one of its nodes in the given set.
+  /// Links with both of their nodes in the set are only yielded once.
+  pub fn incident_links<'a>(&'a self, nodes: &'a IdSet<Node>) -> impl Iterator<Item = (UOrd<Id<Node>>, &'a Link)> + 'a {
+    nodes.iter()
+      .filter_map(|id| self.get_node_neighbors(id).map(|neighbors| (id, neighbors)))
+      .flat_map(move |(id, neighbors)| {
+        neighbors.iter()
+          // links internal to the set are yielded by their lesser node
+          .filter(move |&neighbor| !(nodes.contains(neighbor) && neighbor < id))
+          .map(move |neighbor| {
+            let pair = UOrd::new(id, neighbor);
+            (pair, &self.links[&pair])
+          })
+      })
+  }
+
+  /// Returns an iterator over every distinct pair of nodes that are not linked to each other.
+  /// This visits every pair of nodes in the graph, so it runs in O(n²) time.
+  pub fn missing_links(&self) -> impl Iterator<Item = UOrd<Id<Node>>> + '_ {
+    self.nodes.iter().flat_map(move |(&id, node_inner)| {
+      self.nodes.keys()
+        .filter(move |&&other| id < other && !node_inner.neighbors.contains(other))
+        .map(move |&other| UOrd::new(id, other))
+    })
+  }
+
+  /// Returns the number of pairs of the given node's neighbors that are linked to each other.
+  /// Returns `None` if no node exists for the given `Id`.
+  pub fn triangle_count(&self, id: Id<Node>) -> Option<usize> {
+    let neighbors = self.get_node_neighbors(id)?;
+    let count = neighbors.iter()
+      .flat_map(|a| neighbors.iter().map(move |b| (a, b)))
+      .filter(|&(a, b)| a < b && self.contains_link((a, b)))
+      .count();
+    Some(count)
+  }
+
+  /// Returns the local clustering coefficient of the given node, the fraction of
+  /// pairs of its neighbors that are linked to each other (`0.0` with fewer than two neighbors).
+  /// Returns `None` if no node exists for the given `Id`.
+  pub fn clustering_coefficient(&self, id: Id<Node>) -> Option<f64> {
+    let triangles = self.triangle_count(id)?;
+    let degree = self.node_neighbors_count(id)?;
+    if degree < 2 { return Some(0.0) };
+    let pairs = degree * (degree - 1) / 2;
+    Some(triangles as f64 / pairs as f64)
+  }
+
+  /// Finds the cheapest path between two nodes using Dijkstra's algorithm, where the cost
+  /// of traversing each link is given by `cost`, and `C::default()` is taken to be zero.
+  /// Returns the nodes along the path (including both ends) and its total cost,
+  /// or `None` if either node does not exist or no path connects them.
+  ///
+  /// Negative costs are not supported and may produce incorrect results.
+  pub fn shortest_path_weighted<C>(&self, from: Id<Node>, to: Id<Node>, cost: impl Fn(&Link) -> C) -> Option<(Vec<Id<Node>>, C)>
+  where C: Ord + Add<Output = C> + Copy + Default {
+    if !self.contains_node(from) || !self.contains_node(to) { return None };
+
+    let mut costs: IntMap<Id<Node>, C> = IntMap::default();
+    let mut previous: IntMap<Id<Node>, Id<Node>> = IntMap::default();
+    let mut heap = BinaryHeap::new();
+    costs.insert(from, C::default());
+    heap.push(Reverse((C::default(), from)));
+
+    while let Some(Reverse((current_cost, id))) = heap.pop() {
+      if id == to {
+        let mut path = vec![to];
+        while let Some(&id) = previous.get(path.last().unwrap()) {
+          path.push(id);
+        };
+
+        path.reverse();
+        return Some((path, current_cost));
+      };
+
+      if costs.get(&id).map_or(false, |&best| current_cost > best) { continue };
+      for neighbor in self.nodes[&id].neighbors.iter() {
+        let next_cost = current_cost + cost(&self.links[&UOrd::new(id, neighbor)]);
+        if costs.get(&neighbor).map_or(true, |&best| next_cost < best) {
+          costs.insert(neighbor, next_cost);
+          previous.insert(neighbor, id);
+          heap.push(Reverse((next_cost, neighbor)));
+        };
+      };
+    };
+
+    None
+  }
+
+  /// Returns true if the nodes of this graph can be split into two sets such that
+  /// every link connects a node from one set to a node from the other.
+  pub fn is_bipartite(&self) -> bool {
+    self.bipartite_coloring().is_some()
+  }
+
+  /// Splits the nodes of this graph into two sets such that every link connects
+  /// a node from one set to a node from the other, coloring each component independently.
+  /// Returns `None` if the graph contains an odd cycle and therefore cannot be split.
+  pub fn bipartite_coloring(&self) -> Option<(IdSet<Node>, IdSet<Node>)> {
+    let mut colors: IntMap<Id<Node>, bool> = IntMap::default();
+    let mut queue = VecDeque::new();
+    for &start in self.nodes.keys() {
+      if colors.contains_key(&start) { continue };
+      colors.insert(start, false);
+      queue.push_back(start);
+
+      while let Some(id) = queue.pop_front() {
+        let color = colors[&id];
+        for neighbor in self.nodes[&id].neighbors.iter() {
+          match colors.get(&neighbor) {
+            Some(&neighbor_color) if neighbor_color == color => return None,
+            Some(_) => (),
+            None => {
+              colors.insert(neighbor, !color);
+              queue.push_back(neighbor);
+            }
+          };
+        };
+      };
+    };
+
+    let mut left = IdSet::default();
+    let mut right = IdSet::default();
+    for (id, color) in colors {
+      if color { right.insert(id) } else { left.insert(id) };
+    };
+
+    Some((left, right))
+  }
+
+  /// Returns every link in this graph as a pair of node IDs, with the lesser ID first,
+  /// in ascending order. Nodes without any links are not represented.
+  pub fn to_edge_list(&self) -> Vec<(Id<Node>, Id<Node>)> {
+    let mut edges = self.links.keys()
+      .map(|nodes| nodes.into_tuple())
+      .collect::<Vec<_>>();
+    edges.sort_unstable();
+    edges
+  }
+
+  /// Returns every node in this graph alongside the IDs of its neighbors,
+  /// with both nodes and neighbors in ascending order.
+  pub fn to_adjacency_list(&self) -> Vec<(Id<Node>, Vec<Id<Node>>)> {
+    let mut adjacency = self.nodes.iter()
+      .map(|(&id, node_inner)| {
+        let mut neighbors = node_inner.neighbors.iter().collect::<Vec<_>>();
+        neighbors.sort_unstable();
+        (id, neighbors)
+      })
+      .collect::<Vec<_>>();
+    adjacency.sort_unstable_by_key(|&(id, _)| id);
+    adjacency
+  }
+
+  /// Renders this graph as a Graphviz DOT document, naming each node by its raw ID
+  /// and labeling nodes and links with their values.
+  pub fn to_dot(&self) -> String
+  where Node: fmt::Display, Link: fmt::Display {
+    use std::fmt::Write;
+
+    fn escape(value: impl fmt::Display) -> String {
+      value.to_string().replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    let mut nodes = self.nodes.iter().collect::<Vec<_>>();
+    nodes.sort_unstable_by_key(|&(&id, _)| id);
+    let mut links = self.links.iter().collect::<Vec<_>>();
+    links.sort_unstable_by_key(|&(&nodes, _)| nodes.into_tuple());
+
+    let mut dot = String::from("graph {\n");
+    for (id, node_inner) in nodes {
+      writeln!(dot, "  {} [label=\"{}\"];", id.into_raw(), escape(&node_inner.value)).unwrap();
+    };
+
+    for (nodes, value) in links {
+      let (node1, node2) = nodes.into_tuple();
+      writeln!(dot, "  {} -- {} [label=\"{}\"];", node1.into_raw(), node2.into_raw(), escape(value)).unwrap();
+    };
+
+    dot.push_str("}\n");
+    dot
+  }
+
+  /// Returns the greatest number of links between the given node and any other node in its component.
+  /// Returns `None` if no node exists for the given `Id`.
+  pub fn eccentricity(&self, id: Id<Node>) -> Option<usize> {
+    if !self.contains_node(id) { return None };
+
+    let mut visited = IdSet::default();
+    let mut queue = VecDeque::new();
+    let mut eccentricity = 0;
+    visited.insert(id);
+    queue.push_back((id, 0));
+    while let Some((id, distance)) = queue.pop_front() {
+      eccentricity = eccentricity.max(distance);
+      for neighbor in self.nodes[&id].neighbors.iter() {
+        if visited.insert(neighbor) {
+          queue.push_back((neighbor, distance + 1));
+        };
+      };
+    };
+
+    Some(eccentricity)
+  }
+
+  /// Returns the greatest eccentricity of any node in this graph.
+  /// For a disconnected graph, this is the greatest diameter of any of its components.
+  /// Returns `None` if the graph has no nodes.
+  pub fn diameter(&self) -> Option<usize> {
+    self.nodes_ids().filter_map(|id| self.eccentricity(id)).max()
+  }
+
+  /// Returns the nodes along some cycle in this graph, where each node is linked to the next
+  /// and the last is linked to the first, or `None` if the graph is a forest.
+  pub fn find_cycle(&self) -> Option<Vec<Id<Node>>> {
+    let neighbors_of = |id: Id<Node>| self.nodes[&id].neighbors.iter().collect::<Vec<_>>();
+    let mut parents: IntMap<Id<Node>, Option<Id<Node>>> = IntMap::default();
+    let mut roots = self.nodes_ids().collect::<Vec<_>>();
+    roots.sort_unstable();
+    for root in roots {
+      if parents.contains_key(&root) { continue };
+      parents.insert(root, None);
+
+      let mut stack = vec![(root, neighbors_of(root), 0)];
+      while let Some((id, neighbors, index)) = stack.last_mut() {
+        let id = *id;
+        let Some(&next) = neighbors.get(*index) else {
+          stack.pop();
+          continue;
+        };
+
+        *index += 1;
+        if Some(next) == parents[&id] { continue };
+        if parents.contains_key(&next) {
+          // a link to a visited node other than the parent leads back to an ancestor
+          let mut cycle = vec![id];
+          let mut current = id;
+          while current != next {
+            current = parents[&current].unwrap();
+            cycle.push(current);
+          };
+
+          return Some(cycle);
+        };
+
+        parents.insert(next, Some(id));
+        stack.push((next, neighbors_of(next), 0));
+      };
+    };
+
+    None
+  }
+
+  /// Returns the links of a breadth-first spanning forest of this graph, containing one tree per
+  /// connected component (so `nodes_count` minus the number of components links in total).
+  pub fn spanning_tree(&self) -> Vec<UOrd<Id<Node>>> {
+    let mut visited = IdSet::default();
+    let mut tree = Vec::new();
+    let mut queue = VecDeque::new();
+    let mut roots = self.nodes_ids().collect::<Vec<_>>();
+    roots.sort_unstable();
+    for root in roots {
+      if !visited.insert(root) { continue };
+      queue.push_back(root);
+      while let Some(id) = queue.pop_front() {
+        for neighbor in self.nodes[&id].neighbors.iter() {
+          if visited.insert(neighbor) {
+            tree.push(UOrd::new(id, neighbor));
+            queue.push_back(neighbor);
+          };
+        };
+      };
+    };
+
+    tree
+  }
+
+  /// Returns the links of a minimum spanning forest of this graph using Kruskal's algorithm,
+  /// where the cost of each link is given by `cost`. Ties are broken by the links' node IDs.
+  pub fn minimum_spanning_tree<C: Ord>(&self, cost: impl Fn(&Link) -> C) -> Vec<UOrd<Id<Node>>> {
+    fn find<Node>(parents: &mut IntMap<Id<Node>, Id<Node>>, id: Id<Node>) -> Id<Node> {
+      let mut root = id;
+      while let Some(&parent) = parents.get(&root) {
+        root = parent;
+      };
+
+      // compress the path so that later lookups are faster
+      let mut id = id;
+      while id != root {
+        id = parents.insert(id, root).unwrap();
+      };
+
+      root
+    }
+
+    let mut links = self.links.iter()
+      .map(|(&nodes, value)| (cost(value), nodes.into_tuple()))
+      .collect::<Vec<_>>();
+    links.sort_unstable();
+
+    let mut parents: IntMap<Id<Node>, Id<Node>> = IntMap::default();
+    let mut tree = Vec::new();
+    for (_, (node1, node2)) in links {
+      let root1 = find(&mut parents, node1);
+      let root2 = find(&mut parents, node2);
+      if root1 != root2 {
+        parents.insert(root1, root2);
+        tree.push(UOrd::new(node1, node2));
+      };
+    };
+
+    tree
+  }
+
+  /// Returns every link whose removal would disconnect its two nodes from each other, in ascending order.
+  pub fn bridges(&self) -> Vec<UOrd<Id<Node>>> {
+    let low_link = self.low_link();
+    let mut bridges = low_link.tree_links.iter()
+      .filter(|&&(parent, child)| low_link.low[&child] > low_link.discovery[&parent])
+      .map(|&(parent, child)| UOrd::new(parent, child))
+      .collect::<Vec<_>>();
+    bridges.sort_unstable_by_key(|nodes| nodes.into_tuple());
+    bridges
+  }
+
+  /// Returns every node whose removal would increase the number of connected components.
+  pub fn articulation_points(&self) -> IdSet<Node> {
+    let low_link = self.low_link();
+    let children = low_link.tree_links.iter()
+      .map(|&(_, child)| child)
+      .collect::<IdSet<Node>>();
+    let mut root_children: IntMap<Id<Node>, usize> = IntMap::default();
+    let mut points = IdSet::default();
+    for &(parent, child) in low_link.tree_links.iter() {
+      if !children.contains(parent) {
+        // a root is only an articulation point if it has multiple subtrees
+        *root_children.entry(parent).or_insert(0) += 1;
+      } else if low_link.low[&child] >= low_link.discovery[&parent] {
+        points.insert(parent);
+      };
+    };
+
+    points.extend(root_children.into_iter().filter(|&(_, count)| count > 1).map(|(root, _)| root));
+    points
+  }
+
+  /// Performs an iterative depth-first search over every component of the graph,
+  /// recording discovery times and low-link values for Tarjan's algorithms.
+  fn low_link(&self) -> LowLink<Node> {
+    let mut low_link = LowLink {
+      discovery: IntMap::default(),
+      low: IntMap::default(),
+      tree_links: Vec::new()
+    };
+
+    let neighbors_of = |id: Id<Node>| self.nodes[&id].neighbors.iter().collect::<Vec<_>>();
+    let mut time = 0;
+    let mut roots = self.nodes_ids().collect::<Vec<_>>();
+    roots.sort_unstable();
+    for root in roots {
+      if low_link.discovery.contains_key(&root) { continue };
+      low_link.discovery.insert(root, time);
+      low_link.low.insert(root, time);
+      time += 1;
+
+      let mut stack = vec![(root, None, neighbors_of(root), 0)];
+      while let Some((id, parent, neighbors, index)) = stack.last_mut() {
+        let (id, parent) = (*id, *parent);
+        if let Some(&next) = neighbors.get(*index) {
+          *index += 1;
+          if Some(next) == parent { continue };
+          if let Some(&discovery) = low_link.discovery.get(&next) {
+            let low = low_link.low.get_mut(&id).unwrap();
+            *low = (*low).min(discovery);
+          } else {
+            low_link.discovery.insert(next, time);
+            low_link.low.insert(next, time);
+            time += 1;
+            stack.push((next, Some(id), neighbors_of(next), 0));
+          };
+        } else {
+          stack.pop();
+          if let Some(parent) = parent {
+            let child_low = low_link.low[&id];
+            let low = low_link.low.get_mut(&parent).unwrap();
+            *low = (*low).min(child_low);
+            low_link.tree_links.push((parent, id));
+          };
+        };
+      };
+    };
+
+    low_link
+  }
+
   fn get_node_neighbors_mut(&mut self, id: Id<Node>) -> Option<&mut IdSet<Node>> {
     self.nodes.get_mut(&id).map(|inner_node| &mut inner_node.neighbors)
   }
@@ -106,14 +589,36 @@ impl<Node, Link> Graph<Node, Link> {
 
   /// Unlinks one half of a node pair in the node's `neighbors` list.
   fn unlink_node_neighbor(&mut self, node: Id<Node>, neighbor: Id<Node>) {
-    self.get_node_neighbors_mut(node).unwrap().remove(&neighbor);
+    self.get_node_neighbors_mut(node).unwrap().remove(neighbor);
   }
 
   /// Unlinks both halves of a node pair in the node's `neighbors` list.
   fn unlink_node_neighbors(&mut self, nodes: UOrd<Id<Node>>) {
     let (node1, node2) = nodes.into_tuple();
-    self.get_node_neighbors_mut(node1).unwrap().remove(&node2);
-    self.get_node_neighbors_mut(node2).unwrap().remove(&node1);
+    self.get_node_neighbors_mut(node1).unwrap().remove(node2);
+    self.get_node_neighbors_mut(node2).unwrap().remove(node1);
+  }
+
+  /// Removes every node for which the predicate returns `false`, along with all links to it.
+  pub fn retain_nodes<F: FnMut(Id<Node>, &Node) -> bool>(&mut self, mut pred: F) {
+    let removed = self.nodes()
+      .filter(|&(id, value)| !pred(id, value))
+      .map(|(id, _)| id)
+      .collect::<Vec<_>>();
+    for id in removed {
+      self.remove_node(id);
+    };
+  }
+
+  /// Removes every link for which the predicate returns `false`, updating the neighbors of its nodes.
+  pub fn retain_links<F: FnMut(UOrd<Id<Node>>, &Link) -> bool>(&mut self, mut pred: F) {
+    let removed = self.links.iter()
+      .filter(|&(&nodes, value)| !pred(nodes, value))
+      .map(|(&nodes, _)| nodes)
+      .collect::<Vec<_>>();
+    for nodes in removed {
+      self.remove_link(nodes);
+    };
   }
 
   /// Removes all nodes that have no links.
@@ -145,6 +650,39 @@ impl<Node, Link> Graph<Node, Link> {
     self.links.len()
   }
 
+  /// Returns the lowest ID of any node whose value satisfies the given predicate.
+  pub fn find_node<F: Fn(&Node) -> bool>(&self, pred: F) -> Option<Id<Node>> {
+    self.nodes()
+      .filter(|&(_, value)| pred(value))
+      .map(|(id, _)| id)
+      .min()
+  }
+
+  /// Returns the IDs of all nodes whose values satisfy the given predicate, in ascending order.
+  pub fn find_nodes<F: Fn(&Node) -> bool>(&self, pred: F) -> Vec<Id<Node>> {
+    let mut ids = self.nodes()
+      .filter(|&(_, value)| pred(value))
+      .map(|(id, _)| id)
+      .collect::<Vec<_>>();
+    ids.sort_unstable();
+    ids
+  }
+
+  /// Returns an iterator over each node's ID, value and number of neighbors.
+  pub fn nodes_with_degree(&self) -> impl Iterator<Item = (Id<Node>, &Node, usize)> + '_ {
+    self.nodes.iter().map(|(&id, node_inner)| (id, &node_inner.value, node_inner.neighbors.len()))
+  }
+
+  /// Counts the nodes whose values satisfy the given predicate.
+  pub fn count_nodes<F: Fn(&Node) -> bool>(&self, pred: F) -> usize {
+    self.nodes_values().filter(|value| pred(value)).count()
+  }
+
+  /// Counts the links whose values satisfy the given predicate.
+  pub fn count_links<F: Fn(&Link) -> bool>(&self, pred: F) -> usize {
+    self.links_values().filter(|value| pred(value)).count()
+  }
+
   /// Gets a reference to the value of a node.
   #[inline]
   pub fn get_node_value(&self, id: Id<Node>) -> Option<&Node> {
@@ -157,6 +695,18 @@ impl<Node, Link> Graph<Node, Link> {
     self.nodes.get_mut(&id).map(|inner_node| &mut inner_node.value)
   }
 
+  /// Gets a mutable reference to the value of a node, first inserting a node (with no links)
+  /// under the given ID with the value produced by `f` if it does not exist.
+  /// The ID is reserved so that it will not be spawned by a later call to `add_node`.
+  pub fn get_node_value_or_insert_with(&mut self, id: Id<Node>, f: impl FnOnce() -> Node) -> &mut Node {
+    if !self.contains_node(id) { self.reserve_id(id) };
+    let node_inner = self.nodes.entry(id).or_insert_with(|| {
+      NodeInner { value: f(), neighbors: IdSet::default() }
+    });
+
+    &mut node_inner.value
+  }
+
   /// Gets a reference to the value of a link.
   #[inline]
   pub fn get_link_value(&self, id: impl Into<UOrd<Id<Node>>>) -> Option<&Link> {
@@ -170,58 +720,64 @@ impl<Node, Link> Graph<Node, Link> {
   }
 
   #[inline]
-  pub fn nodes(&self) -> Nodes<Node> {
+  pub fn nodes(&self) -> Nodes<'_, Node> {
     Nodes { inner: self.nodes.iter() }
   }
 
   #[inline]
-  pub fn nodes_mut(&mut self) -> NodesMut<Node> {
+  pub fn nodes_mut(&mut self) -> NodesMut<'_, Node> {
     NodesMut { inner: self.nodes.iter_mut() }
   }
 
   #[inline]
-  pub fn nodes_values(&self) -> NodesValues<Node> {
+  pub fn nodes_values(&self) -> NodesValues<'_, Node> {
     NodesValues { inner: self.nodes.values() }
   }
 
   #[inline]
-  pub fn nodes_values_mut(&mut self) -> NodesValuesMut<Node> {
+  pub fn nodes_values_mut(&mut self) -> NodesValuesMut<'_, Node> {
     NodesValuesMut { inner: self.nodes.values_mut() }
   }
 
   #[inline]
-  pub fn nodes_ids(&self) -> NodesIds<Node> {
+  pub fn nodes_ids(&self) -> NodesIds<'_, Node> {
     NodesIds { inner: self.nodes.keys() }
   }
 
   #[inline]
-  pub fn links(&self) -> Links<Node, Link> {
+  pub fn links(&self) -> Links<'_, Node, Link> {
     Links { inner: self.links.iter() }
   }
 
   #[inline]
-  pub fn links_mut(&mut self) -> LinksMut<Node, Link> {
+  pub fn links_mut(&mut self) -> LinksMut<'_, Node, Link> {
     LinksMut { inner: self.links.iter_mut() }
   }
 
   #[inline]
-  pub fn links_values(&self) -> LinksValues<Node, Link> {
+  pub fn links_values(&self) -> LinksValues<'_, Node, Link> {
     LinksValues { inner: self.links.values() }
   }
 
   #[inline]
-  pub fn links_values_mut(&mut self) -> LinksValuesMut<Node, Link> {
+  pub fn links_values_mut(&mut self) -> LinksValuesMut<'_, Node, Link> {
     LinksValuesMut { inner: self.links.values_mut() }
   }
 
   #[inline]
-  pub fn links_ids(&self) -> LinksIds<Node, Link> {
+  pub fn links_ids(&self) -> LinksIds<'_, Node, Link> {
     LinksIds { inner: self.links.keys() }
   }
 
+  /// Rebuilds the neighbor lists and the `IdContext` of a deserialized graph.
+  /// A saved `IdContext` is only honored if it is ahead of every ID in the graph.
   #[cfg(feature = "serde")]
-  fn from_raw(mut nodes: IntMap<Id<Node>, NodeInner<Node>>, links: HashMap<UOrd<Id<Node>>, Link>) -> Self {
-    fn maximize(acc: &mut Option<u64>, value: u64) {
+  fn from_raw(
+    mut nodes: IntMap<Id<Node>, NodeInner<Node>>,
+    links: HashMap<UOrd<Id<Node>>, Link>,
+    id_context: Option<IdContext<Node>>
+  ) -> Self {
+    fn maximize(acc: &mut Option<ids::RawId>, value: ids::RawId) {
       let acc = acc.get_or_insert(value);
       if value > *acc { *acc = value };
     }
@@ -239,9 +795,8 @@ impl<Node, Link> Graph<Node, Link> {
       maximize(&mut highest_id, id.into_raw());
     };
 
-    let current_id = nodes.keys().copied()
-      .chain(links.keys().copied().flatten())
-      .map(Id::into_raw).max().map_or(0, |max| max + 1);
+    let current_id = highest_id.map_or(0, |max| max.saturating_add(1));
+    let current_id = id_context.map_or(current_id, |id_context| id_context.current_id().max(current_id));
     let id_context = IdContext::with_current_id(current_id);
 
     Graph {
@@ -276,6 +831,7 @@ impl<Node: fmt::Debug, Link: fmt::Debug> fmt::Debug for Graph<Node, Link> {
   }
 }
 
+/// Graphs are compared by their node and link values, ignoring the state of their `IdContext`.
 impl<Node: PartialEq, Link: PartialEq> PartialEq for Graph<Node, Link> {
   #[inline]
   fn eq(&self, other: &Self) -> bool {
@@ -285,10 +841,18 @@ impl<Node: PartialEq, Link: PartialEq> PartialEq for Graph<Node, Link> {
 
 impl<Node: Eq, Link: Eq> Eq for Graph<Node, Link> {}
 
+/// The results of [`Graph::low_link`].
+struct LowLink<Node> {
+  discovery: IntMap<Id<Node>, usize>,
+  low: IntMap<Id<Node>, usize>,
+  /// Links of the depth-first search tree as `(parent, child)`, in the order their children finished.
+  tree_links: Vec<(Id<Node>, Id<Node>)>
+}
+
 #[derive(Clone)]
 struct NodeInner<Node> {
   value: Node,
-  neighbors: IntSet<Id<Node>>
+  neighbors: IdSet<Node>
 }
 
 impl<Node: fmt::Debug> fmt::Debug for NodeInner<Node> {
@@ -324,7 +888,7 @@ where Node: serde::Deserialize<'de> {
   fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
   where D: serde::Deserializer<'de> {
     Node::deserialize(deserializer).map(|value| NodeInner {
-      value, neighbors: IntSet::default()
+      value, neighbors: IdSet::default()
     })
   }
 }
@@ -334,9 +898,10 @@ impl<Node, Link> serde::Serialize for Graph<Node, Link>
 where Node: serde::Serialize, Link: serde::Serialize {
   fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
   where S: serde::Serializer {
-    let mut state = serializer.serialize_struct("Graph", 2)?;
+    let mut state = serializer.serialize_struct("Graph", 3)?;
     serde::ser::SerializeStruct::serialize_field(&mut state, "nodes", &self.nodes)?;
     serde::ser::SerializeStruct::serialize_field(&mut state, "links", &self.links)?;
+    serde::ser::SerializeStruct::serialize_field(&mut state, "current_id", &self.id_context)?;
     serde::ser::SerializeStruct::end(state)
   }
 }
@@ -348,7 +913,8 @@ where Node: serde::Deserialize<'de>, Link: serde::Deserialize<'de> {
   where D: serde::Deserializer<'de> {
     enum GraphField {
       Nodes,
-      Links
+      Links,
+      CurrentId
     }
 
     impl<'de> serde::de::Deserialize<'de> for GraphField {
@@ -373,9 +939,10 @@ where Node: serde::Deserialize<'de>, Link: serde::Deserialize<'de> {
         match value {
           0u64 => Ok(GraphField::Nodes),
           1u64 => Ok(GraphField::Links),
+          2u64 => Ok(GraphField::CurrentId),
           _ => Err(serde::de::Error::invalid_value(
             serde::de::Unexpected::Unsigned(value),
-            &"field index 0 <= i < 2"
+            &"field index 0 <= i < 3"
           ))
         }
       }
@@ -386,7 +953,8 @@ where Node: serde::Deserialize<'de>, Link: serde::Deserialize<'de> {
         match value {
           "nodes" => Ok(GraphField::Nodes),
           "links" => Ok(GraphField::Links),
-          _ => Err(serde::de::Error::unknown_field(&value, FIELDS))
+          "current_id" => Ok(GraphField::CurrentId),
+          _ => Err(serde::de::Error::unknown_field(value, FIELDS))
         }
       }
 
@@ -395,6 +963,7 @@ where Node: serde::Deserialize<'de>, Link: serde::Deserialize<'de> {
         match value {
           b"nodes" => Ok(GraphField::Nodes),
           b"links" => Ok(GraphField::Links),
+          b"current_id" => Ok(GraphField::CurrentId),
           _ => {
             let value = String::from_utf8_lossy(value);
             Err(serde::de::Error::unknown_field(&value, FIELDS))
@@ -425,13 +994,16 @@ where Node: serde::Deserialize<'de>, Link: serde::Deserialize<'de> {
           .ok_or(serde::de::Error::invalid_length(0usize, &"struct Graph with 2 elements"))?;
         let links = seq.next_element::<GraphLinks<Node, Link>>()?
           .ok_or(serde::de::Error::invalid_length(1usize, &"struct Graph with 2 elements"))?;
-        Ok(Graph::from_raw(nodes, links))
+        // graphs serialized before `current_id` was introduced lack the third element
+        let id_context = seq.next_element::<IdContext<Node>>()?;
+        Ok(Graph::from_raw(nodes, links, id_context))
       }
 
       fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
       where A: serde::de::MapAccess<'de> {
         let mut nodes = None;
         let mut links = None;
+        let mut id_context = None;
         while let Some(key) = map.next_key::<GraphField>()? {
           match key {
             GraphField::Nodes => {
@@ -441,17 +1013,21 @@ where Node: serde::Deserialize<'de>, Link: serde::Deserialize<'de> {
             GraphField::Links => {
               if links.is_some() { return Err(serde::de::Error::duplicate_field("links")) };
               links = Some(map.next_value::<GraphLinks<Node, Link>>()?);
+            },
+            GraphField::CurrentId => {
+              if id_context.is_some() { return Err(serde::de::Error::duplicate_field("current_id")) };
+              id_context = Some(map.next_value::<IdContext<Node>>()?);
             }
           }
         };
 
         let nodes = nodes.ok_or(serde::de::Error::missing_field("nodes"))?;
         let links = links.ok_or(serde::de::Error::missing_field("links"))?;
-        Ok(Graph::from_raw(nodes, links))
+        Ok(Graph::from_raw(nodes, links, id_context))
       }
     }
 
-    const FIELDS: &[&str] = &["nodes", "links"];
+    const FIELDS: &[&str] = &["nodes", "links", "current_id"];
     deserializer.deserialize_struct("Graph", FIELDS, GraphVisitor {
       marker: std::marker::PhantomData,
       lifetime: std::marker::PhantomData
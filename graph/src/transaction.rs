@@ -0,0 +1,141 @@
+//! A batch of graph edits that can be rolled back as a unit.
+
+use ids::IdContext;
+
+use crate::{Graph, Id, NodeInner, UOrd};
+
+use std::fmt;
+
+
+
+enum Operation<Node, Link> {
+  AddNode(Id<Node>),
+  AddLink(UOrd<Id<Node>>, Option<Link>),
+  RemoveNode(Id<Node>, Node),
+  RemoveLink(UOrd<Id<Node>>, Link)
+}
+
+/// Records edits applied to a graph so that they can be reverted as a group.
+///
+/// Edits are applied to the graph immediately. Calling [`Transaction::commit`] keeps them,
+/// while calling [`Transaction::rollback`] (or dropping the transaction) reverts them,
+/// restoring the graph to the state it was in when the transaction began.
+pub struct Transaction<'g, Node, Link> {
+  graph: &'g mut Graph<Node, Link>,
+  id_context: IdContext<Node>,
+  operations: Vec<Operation<Node, Link>>
+}
+
+impl<'g, Node, Link> Transaction<'g, Node, Link> {
+  pub(crate) fn new(graph: &'g mut Graph<Node, Link>) -> Self {
+    let id_context = graph.id_context.clone();
+    Transaction { graph, id_context, operations: Vec::new() }
+  }
+
+  /// Returns a reference to the graph in its current (uncommitted) state.
+  #[inline]
+  pub fn graph(&self) -> &Graph<Node, Link> {
+    self.graph
+  }
+
+  /// Adds a new node (with no links) to the graph, placing the given value inside it.
+  pub fn add_node(&mut self, value: Node) -> Id<Node> {
+    let id = self.graph.add_node(value);
+    self.operations.push(Operation::AddNode(id));
+    id
+  }
+
+  /// Adds a new link to the graph, placing the given value inside it.
+  /// Returns `true` if the link already existed, in which case its previous value
+  /// is kept by the transaction so that it can be restored on rollback.
+  /// Panics when attempting to link a node to itself.
+  pub fn add_link(&mut self, value: Link, nodes: impl Into<UOrd<Id<Node>>>) -> bool {
+    let nodes = nodes.into();
+    let previous = self.graph.add_link(value, nodes);
+    let replaced = previous.is_some();
+    self.operations.push(Operation::AddLink(nodes, previous));
+    replaced
+  }
+
+  /// Tries to remove a node from the graph, as well as all links to it.
+  /// Returns `true` if the node existed.
+  pub fn remove_node(&mut self, id: Id<Node>) -> bool {
+    let neighbors = match self.graph.get_node_neighbors(id) {
+      Some(neighbors) => neighbors.iter().collect::<Vec<_>>(),
+      None => return false
+    };
+
+    for neighbor in neighbors {
+      self.remove_link((id, neighbor));
+    };
+
+    let (value, _) = self.graph.remove_node(id).unwrap();
+    self.operations.push(Operation::RemoveNode(id, value));
+    true
+  }
+
+  /// Tries to remove a link from the graph.
+  /// Returns `true` if the link existed.
+  pub fn remove_link(&mut self, nodes: impl Into<UOrd<Id<Node>>>) -> bool {
+    let nodes = nodes.into();
+    match self.graph.remove_link(nodes) {
+      Some(value) => {
+        self.operations.push(Operation::RemoveLink(nodes, value));
+        true
+      },
+      None => false
+    }
+  }
+
+  /// Keeps all edits made through this transaction.
+  #[inline]
+  pub fn commit(mut self) {
+    self.operations.clear();
+    self.id_context = self.graph.id_context.clone();
+  }
+
+  /// Reverts all edits made through this transaction.
+  #[inline]
+  pub fn rollback(self) {
+    // dropping the transaction performs the rollback
+  }
+
+  fn revert(&mut self) {
+    while let Some(operation) = self.operations.pop() {
+      match operation {
+        Operation::AddNode(id) => {
+          self.graph.remove_node(id);
+        },
+        Operation::AddLink(nodes, Some(previous)) => {
+          self.graph.links.insert(nodes, previous);
+        },
+        Operation::AddLink(nodes, None) => {
+          self.graph.remove_link(nodes);
+        },
+        Operation::RemoveNode(id, value) => {
+          self.graph.nodes.insert(id, NodeInner { value, neighbors: Default::default() });
+        },
+        Operation::RemoveLink(nodes, value) => {
+          self.graph.add_link(value, nodes);
+        }
+      };
+    };
+
+    self.graph.id_context = self.id_context.clone();
+  }
+}
+
+impl<'g, Node, Link> Drop for Transaction<'g, Node, Link> {
+  fn drop(&mut self) {
+    self.revert();
+  }
+}
+
+impl<'g, Node: fmt::Debug, Link: fmt::Debug> fmt::Debug for Transaction<'g, Node, Link> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Transaction")
+      .field("graph", &self.graph)
+      .field("operations", &self.operations.len())
+      .finish()
+  }
+}
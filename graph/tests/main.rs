@@ -4,7 +4,7 @@ extern crate ron;
 #[cfg(feature = "serde")]
 extern crate serde;
 
-use graph::Graph;
+use graph::{DecodeError, Graph, Id, UOrd};
 
 #[test]
 fn main() {
@@ -66,3 +66,542 @@ fn test_serde_roundtrip(graph1: &Graph<String, String>) {
   let links2 = graph2.links().collect::<HashMap<_, _>>();
   assert_eq!(links1, links2);
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_id_context() {
+  let mut graph1: Graph<String, String> = Graph::new();
+  let n0 = graph1.add_node("node 0".to_owned());
+  let n1 = graph1.add_node("node 1".to_owned());
+  let n2 = graph1.add_node("node 2".to_owned());
+  graph1.add_link("link 0".to_owned(), (n0, n1));
+  graph1.add_link("link 1".to_owned(), (n1, n2));
+  graph1.remove_node(n2).unwrap();
+
+  let string = ron::to_string(&graph1).expect("failed to serialize");
+  let mut graph2: Graph<String, String> = ron::from_str(&string).expect("failed to deserialize");
+  test_neighbors(&graph2);
+  assert_eq!(graph2.add_node("node 3".to_owned()), graph1.add_node("node 3".to_owned()));
+  assert!(graph2.add_node("node 4".to_owned()) > n2);
+
+  // graphs serialized without `current_id` fall back to the highest ID present
+  let graph3: Graph<String, String> = ron::from_str(r#"(nodes: {0: "node 0", 4: "node 4"}, links: {(0, 4): "link"})"#)
+    .expect("failed to deserialize");
+  assert_eq!(graph3.clone().add_node("node 5".to_owned()).into_raw(), 5);
+}
+
+#[test]
+fn incident_links() {
+  let mut g: Graph<(), &str> = Graph::new();
+  let nodes = (0..5).map(|_| g.add_node(())).collect::<Vec<_>>();
+  g.add_link("internal", (nodes[0], nodes[1]));
+  g.add_link("boundary", (nodes[1], nodes[2]));
+  g.add_link("boundary", (nodes[3], nodes[0]));
+  g.add_link("external", (nodes[3], nodes[4]));
+
+  let selection = [nodes[0], nodes[1]].into_iter().collect::<graph::IdSet<()>>();
+  let incident = g.incident_links(&selection).collect::<Vec<_>>();
+  assert_eq!(incident.len(), 3);
+  for (pair, &value) in incident {
+    let inside = pair.iter().filter(|&&id| selection.contains(id)).count();
+    match value {
+      "internal" => assert_eq!(inside, 2),
+      "boundary" => assert_eq!(inside, 1),
+      _ => panic!("unexpected link {value:?}")
+    };
+  };
+}
+
+#[test]
+fn with_capacity() {
+  fn build(mut g: Graph<usize, usize>) -> Graph<usize, usize> {
+    let nodes = (0..8).map(|i| g.add_node(i)).collect::<Vec<_>>();
+    for (i, pair) in nodes.windows(2).enumerate() {
+      g.add_link(i, (pair[0], pair[1]));
+    };
+
+    g
+  }
+
+  let g1 = build(Graph::new());
+  let g2 = build(Graph::with_capacity(8, 7));
+  test_neighbors(&g2);
+  assert_eq!(g1, g2);
+  assert_eq!(g1.nodes_ids().max(), g2.nodes_ids().max());
+}
+
+#[test]
+fn reassign_node_id() {
+  let mut g: Graph<&str, &str> = Graph::new();
+  let a = g.add_node("a");
+  let b = g.add_node("b");
+  let c = g.add_node("c");
+  g.add_link("a-b", (a, b));
+  g.add_link("b-c", (b, c));
+
+  let new = graph::Id::from_raw(100);
+  assert!(!g.reassign_node_id(new, a));
+  assert!(!g.reassign_node_id(b, c));
+  assert!(g.reassign_node_id(b, new));
+  test_neighbors(&g);
+
+  assert!(!g.contains_node(b));
+  assert_eq!(g.get_node_value(new), Some(&"b"));
+  assert_eq!(g.get_link_value((a, new)), Some(&"a-b"));
+  assert_eq!(g.get_link_value((new, c)), Some(&"b-c"));
+  assert!(!g.contains_link((a, b)));
+  assert!(g.add_node("d") > new);
+}
+
+#[test]
+fn reserve_max_id() {
+  let mut g: Graph<&str, &str> = Graph::new();
+  let a = g.add_node("a");
+  let max = graph::Id::from_raw(u64::MAX);
+  assert!(g.reassign_node_id(a, max));
+  assert_eq!(*g.get_node_value_or_insert_with(max, || "b"), "a");
+
+  let mut g: Graph<&str, &str> = Graph::new();
+  assert_eq!(*g.get_node_value_or_insert_with(max, || "b"), "b");
+  assert_eq!(g.get_node_value(max), Some(&"b"));
+}
+
+#[test]
+fn missing_links() {
+  let mut g: Graph<(), ()> = Graph::new();
+  let a = g.add_node(());
+  let b = g.add_node(());
+  let c = g.add_node(());
+  g.add_link((), (a, b));
+
+  let missing = g.missing_links().collect::<std::collections::HashSet<_>>();
+  assert_eq!(missing.len(), 2);
+  assert!(missing.contains(&graph::UOrd::new(a, c)));
+  assert!(missing.contains(&graph::UOrd::new(b, c)));
+}
+
+#[test]
+fn clustering_coefficient() {
+  let mut g: Graph<(), ()> = Graph::new();
+  let a = g.add_node(());
+  let b = g.add_node(());
+  let c = g.add_node(());
+  g.add_link((), (a, b));
+  g.add_link((), (b, c));
+  g.add_link((), (c, a));
+  assert_eq!(g.triangle_count(a), Some(1));
+  assert_eq!(g.clustering_coefficient(a), Some(1.0));
+
+  let d = g.add_node(());
+  g.add_link((), (a, d));
+  assert_eq!(g.triangle_count(a), Some(1));
+  assert_eq!(g.clustering_coefficient(a), Some(1.0 / 3.0));
+  assert_eq!(g.clustering_coefficient(d), Some(0.0));
+
+  g.remove_node(d);
+  assert_eq!(g.triangle_count(d), None);
+}
+
+#[test]
+fn partial_eq() {
+  let mut g1: Graph<&str, &str> = Graph::new();
+  let a = g1.add_node("a");
+  let b = g1.add_node("b");
+  g1.add_link("a-b", (a, b));
+  assert_eq!(g1, g1.clone());
+
+  // spawning and discarding an ID only advances the `IdContext`
+  let mut g2 = g1.clone();
+  let c = g2.add_node("c");
+  g2.remove_node(c);
+  assert_eq!(g1, g2);
+
+  g2.add_node("d");
+  assert_ne!(g1, g2);
+}
+
+#[test]
+fn contract_link() {
+  let mut g: Graph<u32, &str> = Graph::new();
+  let a = g.add_node(1);
+  let b = g.add_node(2);
+  let c = g.add_node(4);
+  let d = g.add_node(8);
+  g.add_link("a-b", (a, b));
+  g.add_link("a-c", (a, c));
+  g.add_link("b-c", (b, c));
+  g.add_link("b-d", (b, d));
+
+  assert_eq!(g.contract_link((c, d), |x, y| x + y), None);
+  assert_eq!(g.contract_link((b, a), |x, y| x + y), Some(a));
+  test_neighbors(&g);
+
+  assert!(!g.contains_node(b));
+  assert_eq!(g.get_node_value(a), Some(&3));
+  assert_eq!(g.nodes_count(), 3);
+  assert_eq!(g.links_count(), 2);
+  assert_eq!(g.get_link_value((a, c)), Some(&"a-c"));
+  assert_eq!(g.get_link_value((a, d)), Some(&"b-d"));
+}
+
+#[test]
+fn shortest_path_weighted() {
+  let mut graph = Graph::<&str, u32>::new();
+  let a = graph.add_node("a");
+  let b = graph.add_node("b");
+  let c = graph.add_node("c");
+  let d = graph.add_node("d");
+  let e = graph.add_node("e");
+  graph.add_link(10, (a, d));
+  graph.add_link(1, (a, b));
+  graph.add_link(2, (b, c));
+  graph.add_link(3, (c, d));
+
+  assert_eq!(graph.shortest_path_weighted(a, d, |&cost| cost), Some((vec![a, b, c, d], 6)));
+  assert_eq!(graph.shortest_path_weighted(d, a, |&cost| cost), Some((vec![d, c, b, a], 6)));
+  assert_eq!(graph.shortest_path_weighted(a, d, |_| 1), Some((vec![a, d], 1)));
+  assert_eq!(graph.shortest_path_weighted(a, a, |&cost| cost), Some((vec![a], 0)));
+  assert_eq!(graph.shortest_path_weighted(a, e, |&cost| cost), None);
+  graph.remove_node(e);
+  assert_eq!(graph.shortest_path_weighted(a, e, |&cost| cost), None);
+}
+
+#[test]
+fn bipartite() {
+  let mut graph = Graph::<(), ()>::new();
+  let [a, b, c, d] = [(); 4].map(|()| graph.add_node(()));
+  let e = graph.add_node(());
+  graph.add_link((), (a, b));
+  graph.add_link((), (b, c));
+  graph.add_link((), (c, d));
+  graph.add_link((), (d, a));
+
+  assert!(graph.is_bipartite());
+  let (left, right) = graph.bipartite_coloring().unwrap();
+  assert_eq!(left.len() + right.len(), 5);
+  for (nodes, ()) in graph.links() {
+    let (x, y) = nodes.into_tuple();
+    assert_ne!(left.contains(x), left.contains(y));
+    assert_ne!(right.contains(x), right.contains(y));
+  };
+
+  assert!(left.contains(e) || right.contains(e));
+
+  graph.add_link((), (a, c));
+  assert!(!graph.is_bipartite());
+  assert_eq!(graph.bipartite_coloring(), None);
+}
+
+#[test]
+fn transaction() {
+  let mut graph = Graph::<&str, u32>::new();
+  let a = graph.add_node("a");
+  let b = graph.add_node("b");
+  let c = graph.add_node("c");
+  graph.add_link(1, (a, b));
+  graph.add_link(2, (b, c));
+  let original = graph.clone();
+
+  let mut transaction = graph.transaction();
+  let d = transaction.add_node("d");
+  assert!(!transaction.add_link(3, (c, d)));
+  assert!(transaction.add_link(4, (a, b)));
+  assert!(transaction.remove_node(b));
+  assert!(!transaction.remove_node(b));
+  assert!(transaction.remove_link((c, d)));
+  assert!(!transaction.remove_link((a, c)));
+  assert_eq!(transaction.graph().nodes_count(), 3);
+  assert_eq!(transaction.graph().links_count(), 0);
+  transaction.rollback();
+
+  assert_eq!(graph, original);
+  test_neighbors(&graph);
+  assert_eq!(graph.get_link_value((a, b)), Some(&1));
+  assert_eq!(graph.add_node("d"), d);
+
+  let mut transaction = graph.transaction();
+  transaction.remove_node(a);
+  transaction.commit();
+  assert!(!graph.contains_node(a));
+  assert_eq!(graph.links_count(), 1);
+  test_neighbors(&graph);
+}
+
+#[test]
+fn count_by_predicate() {
+  let mut graph = Graph::<u32, u32>::new();
+  let [a, b, c, d] = [1, 2, 3, 4].map(|value| graph.add_node(value));
+  graph.add_link(10, (a, b));
+  graph.add_link(20, (b, c));
+  graph.add_link(30, (c, d));
+
+  assert_eq!(graph.count_nodes(|&value| value % 2 == 0), 2);
+  assert_eq!(graph.count_nodes(|&value| value > 4), 0);
+  assert_eq!(graph.count_links(|&value| value >= 20), 2);
+  assert_eq!(graph.count_links(|_| true), graph.links_count());
+}
+
+#[test]
+fn get_node_value_or_insert_with() {
+  let mut graph = Graph::<u32, ()>::new();
+  let a = graph.add_node(1);
+  *graph.get_node_value_or_insert_with(a, || unreachable!()) += 1;
+  assert_eq!(graph.get_node_value(a), Some(&2));
+
+  let b = Id::from_raw(5);
+  assert_eq!(*graph.get_node_value_or_insert_with(b, || 10), 10);
+  *graph.get_node_value_or_insert_with(b, || unreachable!()) += 1;
+  assert_eq!(graph.get_node_value(b), Some(&11));
+  assert_eq!(graph.nodes_count(), 2);
+
+  let c = graph.add_node(3);
+  assert!(c > b);
+  graph.add_link((), (a, b));
+  test_neighbors(&graph);
+}
+
+#[test]
+fn edge_list() {
+  let mut graph = Graph::<(), ()>::new();
+  let [a, b, c, d] = [(); 4].map(|()| graph.add_node(()));
+  graph.add_link((), (c, a));
+  graph.add_link((), (b, a));
+  graph.add_link((), (d, c));
+
+  let edges = graph.to_edge_list();
+  assert_eq!(edges, [(a, b), (a, c), (c, d)]);
+  assert_eq!(graph.to_adjacency_list(), [(a, vec![b, c]), (b, vec![a]), (c, vec![a, d]), (d, vec![c])]);
+
+  let rebuilt = Graph::<(), ()>::from_edge_list(edges.iter().copied());
+  assert_eq!(rebuilt, graph);
+  assert_eq!(rebuilt.to_edge_list(), edges);
+  test_neighbors(&rebuilt);
+}
+
+#[test]
+fn to_dot() {
+  let mut graph = Graph::<&str, u32>::new();
+  let a = graph.add_node("a");
+  let b = graph.add_node("say \"b\"");
+  let c = graph.add_node("c");
+  graph.add_link(5, (b, a));
+  graph.add_link(7, (b, c));
+
+  let dot = graph.to_dot();
+  assert!(dot.starts_with("graph {\n"));
+  assert!(dot.ends_with("}\n"));
+  assert!(dot.contains("  0 [label=\"a\"];\n"));
+  assert!(dot.contains("  1 [label=\"say \\\"b\\\"\"];\n"));
+  assert!(dot.contains("  0 -- 1 [label=\"5\"];\n"));
+  assert!(dot.contains("  1 -- 2 [label=\"7\"];\n"));
+  assert_eq!(dot.lines().count(), 7);
+}
+
+#[test]
+fn find_node() {
+  let mut graph = Graph::<String, ()>::new();
+  let london = graph.add_node("London".to_owned());
+  let paris = graph.add_node("Paris".to_owned());
+  let lyon = graph.add_node("Lyon".to_owned());
+
+  assert_eq!(graph.find_node(|name| name.contains("ari")), Some(paris));
+  assert_eq!(graph.find_node(|name| name.contains("Berlin")), None);
+  assert_eq!(graph.find_node(|name| name.contains('o')), Some(london));
+  assert_eq!(graph.find_nodes(|name| name.starts_with('L')), [london, lyon]);
+  assert!(graph.find_nodes(|name| name.is_empty()).is_empty());
+}
+
+#[test]
+fn nodes_with_degree() {
+  let mut graph = Graph::<char, ()>::new();
+  let [a, b, c, d] = ['a', 'b', 'c', 'd'].map(|value| graph.add_node(value));
+  graph.add_link((), (a, b));
+  graph.add_link((), (a, c));
+  graph.add_link((), (a, d));
+  graph.add_link((), (b, c));
+
+  let mut degrees = graph.nodes_with_degree().collect::<Vec<_>>();
+  degrees.sort_unstable_by_key(|&(id, _, _)| id);
+  assert_eq!(degrees, [(a, &'a', 3), (b, &'b', 2), (c, &'c', 2), (d, &'d', 1)]);
+  for (id, _, degree) in degrees {
+    assert_eq!(graph.node_neighbors_count(id), Some(degree));
+  };
+}
+
+#[test]
+fn bridges() {
+  let mut graph = Graph::<(), ()>::new();
+  let [a, b, c, d, e, f] = [(); 6].map(|()| graph.add_node(()));
+  graph.add_link((), (a, b));
+  graph.add_link((), (b, c));
+  graph.add_link((), (c, a));
+  graph.add_link((), (d, e));
+  graph.add_link((), (e, f));
+  graph.add_link((), (f, d));
+  graph.add_link((), (c, d));
+  assert_eq!(graph.bridges(), [UOrd::new(c, d)]);
+
+  let g = graph.add_node(());
+  let h = graph.add_node(());
+  graph.add_link((), (g, h));
+  assert_eq!(graph.bridges(), [UOrd::new(c, d), UOrd::new(g, h)]);
+  assert!(Graph::<(), ()>::new().bridges().is_empty());
+}
+
+#[test]
+fn articulation_points() {
+  let mut graph = Graph::<(), ()>::new();
+  let [a, b, c] = [(); 3].map(|()| graph.add_node(()));
+  graph.add_link((), (a, b));
+  graph.add_link((), (b, c));
+  assert_eq!(graph.articulation_points().into_iter().collect::<Vec<_>>(), [b]);
+
+  // the search starts from `a`, so this also covers a root with multiple subtrees
+  let d = graph.add_node(());
+  graph.add_link((), (a, d));
+  let mut points = graph.articulation_points().into_iter().collect::<Vec<_>>();
+  points.sort_unstable();
+  assert_eq!(points, [a, b]);
+
+  graph.add_link((), (c, d));
+  assert!(graph.articulation_points().is_empty());
+}
+
+#[test]
+fn spanning_tree() {
+  let mut graph = Graph::<(), u32>::new();
+  let [a, b, c, d, e, f] = [(); 6].map(|()| graph.add_node(()));
+  graph.add_link(1, (a, b));
+  graph.add_link(5, (b, c));
+  graph.add_link(2, (c, a));
+  graph.add_link(3, (c, d));
+  graph.add_link(4, (d, a));
+  graph.add_link(9, (e, f));
+  let components = 2;
+
+  let tree = graph.spanning_tree();
+  assert_eq!(tree.len(), graph.nodes_count() - components);
+  assert!(tree.iter().all(|&nodes| graph.contains_link(nodes)));
+  // every link of a forest is a bridge, and the forest must cover every node
+  let forest = Graph::<(), ()>::from_edge_list(tree.iter().map(|nodes| nodes.into_tuple()));
+  assert_eq!(forest.bridges().len(), tree.len());
+  assert_eq!(forest.nodes_count(), graph.nodes_count());
+
+  let mut tree = graph.minimum_spanning_tree(|&cost| cost);
+  tree.sort_unstable_by_key(|nodes| nodes.into_tuple());
+  assert_eq!(tree, [UOrd::new(a, b), UOrd::new(a, c), UOrd::new(c, d), UOrd::new(e, f)]);
+}
+
+#[test]
+fn retain_nodes() {
+  let mut graph = Graph::<u32, ()>::new();
+  let ids = (0..8).map(|value| graph.add_node(value)).collect::<Vec<_>>();
+  for (i, &a) in ids.iter().enumerate() {
+    for &b in &ids[i + 1..] {
+      graph.add_link((), (a, b));
+    };
+  };
+
+  graph.retain_nodes(|_, &value| value < 4);
+  test_neighbors(&graph);
+  assert_eq!(graph.nodes_count(), 4);
+  assert_eq!(graph.links_count(), 6);
+  assert!(graph.nodes_values().all(|&value| value < 4));
+  for &id in &ids[..4] {
+    assert_eq!(graph.node_neighbors_count(id), Some(3));
+  };
+}
+
+#[test]
+fn retain_links() {
+  let mut graph = Graph::<(), u32>::new();
+  let [a, b, c, d] = [(); 4].map(|()| graph.add_node(()));
+  graph.add_link(1, (a, b));
+  graph.add_link(8, (a, c));
+  graph.add_link(3, (a, d));
+  graph.add_link(9, (b, c));
+  graph.add_link(2, (c, d));
+
+  graph.retain_links(|_, &weight| weight >= 3);
+  test_neighbors(&graph);
+  assert_eq!(graph.links_count(), 3);
+  assert_eq!(graph.node_neighbors_count(a), Some(2));
+  assert_eq!(graph.node_neighbors_count(b), Some(1));
+  assert_eq!(graph.node_neighbors_count(c), Some(2));
+  assert_eq!(graph.node_neighbors_count(d), Some(1));
+  assert!(!graph.get_node_neighbors(a).unwrap().contains(b));
+
+  graph.retain_links(|nodes, _| !nodes.contains(&a));
+  assert_eq!(graph.to_edge_list(), [(b, c)]);
+  test_neighbors(&graph);
+}
+
+#[test]
+fn eccentricity_and_diameter() {
+  let mut graph = Graph::<(), ()>::new();
+  assert_eq!(graph.diameter(), None);
+
+  let path = (0..5).map(|_| graph.add_node(())).collect::<Vec<_>>();
+  for pair in path.windows(2) {
+    graph.add_link((), (pair[0], pair[1]));
+  };
+
+  assert_eq!(graph.diameter(), Some(graph.links_count()));
+  assert_eq!(graph.eccentricity(path[0]), Some(4));
+  assert_eq!(graph.eccentricity(path[2]), Some(2));
+
+  let lone = graph.add_node(());
+  assert_eq!(graph.eccentricity(lone), Some(0));
+  assert_eq!(graph.diameter(), Some(4));
+  graph.remove_node(lone);
+  assert_eq!(graph.eccentricity(lone), None);
+}
+
+#[test]
+fn find_cycle() {
+  let mut graph = Graph::<(), ()>::new();
+  let [a, b, c, d, e] = [(); 5].map(|()| graph.add_node(()));
+  graph.add_link((), (a, b));
+  graph.add_link((), (a, c));
+  graph.add_link((), (c, d));
+  graph.add_link((), (c, e));
+  assert_eq!(graph.find_cycle(), None);
+
+  graph.add_link((), (d, e));
+  let mut cycle = graph.find_cycle().unwrap();
+  for i in 0..cycle.len() {
+    assert!(graph.contains_link((cycle[i], cycle[(i + 1) % cycle.len()])));
+  };
+
+  cycle.sort_unstable();
+  assert_eq!(cycle, [c, d, e]);
+}
+
+#[test]
+fn bytes_round_trip() {
+  let mut graph = Graph::<[u16; 2], f64>::new();
+  let [a, b, c, d] = [[1, 2], [3, 4], [5, 6], [7, 8]].map(|value| graph.add_node(value));
+  graph.add_link(0.5, (a, b));
+  graph.add_link(1.5, (b, c));
+  graph.add_link(-2.0, (c, a));
+  graph.remove_node(d);
+
+  let bytes = graph.to_bytes();
+  let mut decoded = Graph::<[u16; 2], f64>::from_bytes(&bytes).unwrap();
+  assert_eq!(decoded, graph);
+  assert_eq!(decoded.get_node_value(b), Some(&[3, 4]));
+  assert_eq!(decoded.get_link_value((a, c)), Some(&-2.0));
+  test_neighbors(&decoded);
+  assert_eq!(decoded.to_bytes(), bytes);
+  // the removed node's ID is not handed out again
+  assert_eq!(decoded.add_node([0, 0]), graph.add_node([0, 0]));
+
+  assert_eq!(Graph::<[u16; 2], f64>::from_bytes(&bytes[..bytes.len() - 1]), Err(DecodeError::UnexpectedEnd));
+  let mut trailing = bytes.clone();
+  trailing.push(0);
+  assert_eq!(Graph::<[u16; 2], f64>::from_bytes(&trailing), Err(DecodeError::TrailingBytes));
+
+  let mut graph = Graph::<(), ()>::new();
+  graph.get_node_value_or_insert_with(Id::from_raw(u64::MAX), || ());
+  assert_eq!(Graph::<(), ()>::from_bytes(&graph.to_bytes()), Ok(graph));
+}
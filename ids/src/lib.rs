@@ -2,34 +2,53 @@
 
 pub extern crate nohash_hasher;
 
+#[macro_use]
+mod macros;
+
 #[cfg(feature = "map")]
 pub mod map;
+pub mod repr;
+pub mod set;
+pub mod slot_map;
+#[cfg(feature = "serde")]
+pub mod tagged;
 
 #[cfg(feature = "map")]
 pub use crate::map::IdMap;
+pub use crate::repr::IdRepr;
+pub use crate::set::IdSet;
+pub use crate::slot_map::{GenId, IdSlotMap};
 
-use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::atomic::Ordering as AtomicOrdering;
 use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::cmp::Ordering;
+use std::num::ParseIntError;
+use std::str::FromStr;
 use std::fmt;
 
+use nohash_hasher::IsEnabled;
+
 const ORDERING: AtomicOrdering = AtomicOrdering::SeqCst;
 
+/// The integer type backing [`Id`]s by default, see [`IdRepr`] for the alternatives.
+pub type RawId = u64;
+
 
 
 /// A context for spawning unique IDs.
 #[repr(transparent)]
 #[derive(Debug)]
-pub struct IdContext<F: ?Sized = ()> {
-  current_id: u64,
+pub struct IdContext<F: ?Sized = (), R: IdRepr = RawId> {
+  current_id: R,
   family: PhantomData<F>
 }
 
-impl<F: ?Sized> IdContext<F> {
-  /// Creates a new ID context with an from which it should start counting from.
+impl<F: ?Sized, R: IdRepr> IdContext<F, R> {
+  /// Creates a new ID context that will start counting from the given raw ID,
+  /// allowing a sequence to be resumed from a saved counter.
   #[inline]
-  pub const fn with_current_id(current_id: u64) -> IdContext<F> {
+  pub const fn with_current_id(current_id: R) -> IdContext<F, R> {
     IdContext {
       current_id,
       family: PhantomData
@@ -38,19 +57,46 @@ impl<F: ?Sized> IdContext<F> {
 
   /// Creates a new ID context.
   #[inline]
-  pub const fn new() -> IdContext<F> {
-    Self::with_current_id(0)
+  pub const fn new() -> IdContext<F, R> {
+    Self::with_current_id(R::ZERO)
+  }
+
+  /// Returns the raw value of the next ID this context will spawn.
+  #[inline]
+  pub const fn current_id(&self) -> R {
+    self.current_id
   }
 
   /// Spawns the next unique ID for this context.
-  pub fn next_id(&mut self) -> Id<F> {
+  /// Panics if the context has been exhausted, see [`IdContext::try_next_id`].
+  pub fn next_id(&mut self) -> Id<F, R> {
+    self.try_next_id().expect("id context exhausted")
+  }
+
+  /// Spawns the next unique ID for this context, or returns `None` if the context
+  /// has been exhausted (the counter cannot advance past the maximum raw ID).
+  pub fn try_next_id(&mut self) -> Option<Id<F, R>> {
     let id = self.current_id;
-    self.current_id += 1;
-    Id::from_raw(id)
+    self.current_id = id.checked_add(R::ONE)?;
+    Some(Id::from_raw(id))
+  }
+
+  /// Spawns the given number of unique, contiguous IDs for this context.
+  /// Panics if the context does not have enough IDs left, see [`IdContext::try_next_ids`].
+  pub fn next_ids(&mut self, count: R) -> IdRange<F, R> {
+    self.try_next_ids(count).expect("id context exhausted")
+  }
+
+  /// Spawns the given number of unique, contiguous IDs for this context, or returns `None`
+  /// (leaving the context untouched) if the counter cannot advance that far.
+  pub fn try_next_ids(&mut self, count: R) -> Option<IdRange<F, R>> {
+    let start = self.current_id;
+    self.current_id = start.checked_add(count)?;
+    Some(IdRange::from_raw(start, self.current_id))
   }
 }
 
-impl<F: ?Sized> Clone for IdContext<F> {
+impl<F: ?Sized, R: IdRepr> Clone for IdContext<F, R> {
   #[inline]
   fn clone(&self) -> Self {
     IdContext {
@@ -60,42 +106,103 @@ impl<F: ?Sized> Clone for IdContext<F> {
   }
 }
 
-impl<F: ?Sized> Default for IdContext<F> {
+impl<F: ?Sized, R: IdRepr> Default for IdContext<F, R> {
   #[inline]
-  fn default() -> IdContext<F> {
+  fn default() -> IdContext<F, R> {
     IdContext::new()
   }
 }
 
-unsafe impl<F: ?Sized> Send for IdContext<F> {}
-unsafe impl<F: ?Sized> Sync for IdContext<F> {}
+unsafe impl<F: ?Sized, R: IdRepr> Send for IdContext<F, R> {}
+unsafe impl<F: ?Sized, R: IdRepr> Sync for IdContext<F, R> {}
+
+#[cfg(feature = "serde")]
+impl<F: ?Sized, R> serde::Serialize for IdContext<F, R>
+where R: IdRepr + serde::Serialize {
+  #[inline]
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where S: serde::Serializer {
+    self.current_id.serialize(serializer)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, F: ?Sized, R> serde::Deserialize<'de> for IdContext<F, R>
+where R: IdRepr + serde::Deserialize<'de> {
+  #[inline]
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where D: serde::Deserializer<'de> {
+    R::deserialize(deserializer).map(IdContext::with_current_id)
+  }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, F: ?Sized, R> arbitrary::Arbitrary<'a> for IdContext<F, R>
+where R: IdRepr + arbitrary::Arbitrary<'a> {
+  #[inline]
+  fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+    R::arbitrary(u).map(IdContext::with_current_id)
+  }
+
+  #[inline]
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    R::size_hint(depth)
+  }
+}
 
 
 
+/// An opaque ID belonging to the family `F`, backed by the integer type `R`.
 #[repr(transparent)]
-pub struct Id<F: ?Sized = ()> {
-  id: u64,
+pub struct Id<F: ?Sized = (), R: IdRepr = RawId> {
+  id: R,
   family: PhantomData<F>
 }
 
-impl<F: ?Sized> Id<F> {
+impl<F: ?Sized, R: IdRepr> Id<F, R> {
   #[inline]
-  pub const fn from_raw(id: u64) -> Self {
+  pub const fn from_raw(id: R) -> Self {
     Id { id, family: PhantomData }
   }
 
   #[inline]
-  pub const fn into_raw(self) -> u64 {
+  pub const fn into_raw(self) -> R {
     self.id
   }
 
   #[inline]
-  pub const fn cast<U>(self) -> Id<U> {
+  pub const fn cast<U>(self) -> Id<U, R> {
     Id::from_raw(self.id)
   }
+
+  /// Like [`Id::cast`], but only casts when the given predicate accepts the raw ID.
+  #[inline]
+  pub fn cast_checked<U>(self, valid: impl FnOnce(R) -> bool) -> Option<Id<U, R>> {
+    if valid(self.id) { Some(self.cast()) } else { None }
+  }
+
+  /// Returns a wrapper that displays this ID prefixed by the name of its family, like `Player#42`.
+  #[inline]
+  pub const fn display_tagged(self) -> TaggedDisplay<F, R> where F: IdFamily {
+    TaggedDisplay { id: self }
+  }
+
+  /// Reinterprets a slice of IDs as a slice of their raw values, without copying.
+  #[inline]
+  pub fn as_raw_slice(ids: &[Id<F, R>]) -> &[R] {
+    // SAFETY: `Id<F, R>` is `repr(transparent)` over `R`, so the two have identical layouts
+    unsafe { std::slice::from_raw_parts(ids.as_ptr() as *const R, ids.len()) }
+  }
+
+  /// Reinterprets a slice of raw values as a slice of IDs, without copying.
+  #[inline]
+  pub fn from_raw_slice(raw: &[R]) -> &[Id<F, R>] {
+    // SAFETY: `Id<F, R>` is `repr(transparent)` over `R`, so the two have identical layouts
+    unsafe { std::slice::from_raw_parts(raw.as_ptr() as *const Id<F, R>, raw.len()) }
+  }
 }
 
-impl<F: ?Sized> fmt::Debug for Id<F> {
+impl<F: ?Sized, R: IdRepr> fmt::Debug for Id<F, R> {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     f.debug_tuple("Id")
       .field(&self.id)
@@ -103,50 +210,104 @@ impl<F: ?Sized> fmt::Debug for Id<F> {
   }
 }
 
-impl<F: ?Sized> Clone for Id<F> {
+/// Displays the raw value of this ID.
+impl<F: ?Sized, R: IdRepr> fmt::Display for Id<F, R> {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(&self.id, f)
+  }
+}
+
+/// Parses the raw value of an ID, the inverse of its `Display` implementation.
+impl<F: ?Sized, R: IdRepr> FromStr for Id<F, R> {
+  type Err = ParseIntError;
+
+  #[inline]
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    s.parse::<R>().map(Id::from_raw)
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> Clone for Id<F, R> {
   #[inline]
   fn clone(&self) -> Self {
-    Id::from_raw(self.id)
+    *self
   }
 }
 
-impl<F: ?Sized> Copy for Id<F> {}
+impl<F: ?Sized, R: IdRepr> Copy for Id<F, R> {}
 
-impl<F: ?Sized> PartialEq for Id<F> {
+impl<F: ?Sized, R: IdRepr> PartialEq for Id<F, R> {
   #[inline]
   fn eq(&self, other: &Self) -> bool {
     self.id == other.id
   }
 }
 
-impl<F: ?Sized> Eq for Id<F> {}
+impl<F: ?Sized, R: IdRepr> Eq for Id<F, R> {}
 
-impl<F: ?Sized> PartialOrd for Id<F> {
+impl<F: ?Sized, R: IdRepr> PartialOrd for Id<F, R> {
   #[inline]
   fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-    PartialOrd::partial_cmp(&self.id, &other.id)
+    Some(Ord::cmp(self, other))
   }
 }
 
-impl<F: ?Sized> Ord for Id<F> {
+impl<F: ?Sized, R: IdRepr> Ord for Id<F, R> {
   #[inline]
   fn cmp(&self, other: &Self) -> Ordering {
     Ord::cmp(&self.id, &other.id)
   }
 }
 
-impl<F: ?Sized> Hash for Id<F> {
+impl<F: ?Sized, R: IdRepr> Hash for Id<F, R> {
   #[inline]
   fn hash<H: Hasher>(&self, state: &mut H) {
-    state.write_u64(self.id);
+    self.id.hash(state);
   }
 }
 
-unsafe impl<F: ?Sized> Send for Id<F> {}
-unsafe impl<F: ?Sized> Sync for Id<F> {}
+unsafe impl<F: ?Sized, R: IdRepr> Send for Id<F, R> {}
+unsafe impl<F: ?Sized, R: IdRepr> Sync for Id<F, R> {}
+
+impl<F: ?Sized, R: IdRepr> IsEnabled for Id<F, R> {}
+
+/// Provides a human-readable name for an ID family,
+/// allowing [`Id`]s of that family to be tagged with it.
+pub trait IdFamily {
+  /// The name of this ID family.
+  const NAME: &'static str;
+}
+
+/// Displays an [`Id`] prefixed by the name of its family, see [`Id::display_tagged`].
+pub struct TaggedDisplay<F: ?Sized + IdFamily, R: IdRepr = RawId> {
+  id: Id<F, R>
+}
+
+impl<F: ?Sized + IdFamily, R: IdRepr> fmt::Display for TaggedDisplay<F, R> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}#{}", F::NAME, self.id.id)
+  }
+}
+
+impl<F: ?Sized + IdFamily, R: IdRepr> fmt::Debug for TaggedDisplay<F, R> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(self, f)
+  }
+}
+
+impl<F: ?Sized + IdFamily, R: IdRepr> Clone for TaggedDisplay<F, R> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<F: ?Sized + IdFamily, R: IdRepr> Copy for TaggedDisplay<F, R> {}
 
 #[cfg(feature = "serde")]
-impl<F: ?Sized> serde::Serialize for Id<F> {
+impl<F: ?Sized, R> serde::Serialize for Id<F, R>
+where R: IdRepr + serde::Serialize {
   #[inline]
   fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
   where S: serde::Serializer {
@@ -155,11 +316,303 @@ impl<F: ?Sized> serde::Serialize for Id<F> {
 }
 
 #[cfg(feature = "serde")]
-impl<'de, F: ?Sized> serde::Deserialize<'de> for Id<F> {
+impl<'de, F: ?Sized, R> serde::Deserialize<'de> for Id<F, R>
+where R: IdRepr + serde::Deserialize<'de> {
   #[inline]
   fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
   where D: serde::Deserializer<'de> {
-    u64::deserialize(deserializer).map(Id::from_raw)
+    R::deserialize(deserializer).map(Id::from_raw)
+  }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, F: ?Sized, R> arbitrary::Arbitrary<'a> for Id<F, R>
+where R: IdRepr + arbitrary::Arbitrary<'a> {
+  #[inline]
+  fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+    R::arbitrary(u).map(Id::from_raw)
+  }
+
+  #[inline]
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    R::size_hint(depth)
+  }
+}
+
+
+
+/// A contiguous span of [`Id`]s, from `start` (inclusive) to `end` (exclusive).
+pub struct IdRange<F: ?Sized = (), R: IdRepr = RawId> {
+  start: R,
+  end: R,
+  family: PhantomData<F>
+}
+
+impl<F: ?Sized, R: IdRepr> IdRange<F, R> {
+  #[inline]
+  pub const fn new(start: Id<F, R>, end: Id<F, R>) -> Self {
+    IdRange::from_raw(start.id, end.id)
+  }
+
+  #[inline]
+  pub const fn from_raw(start: R, end: R) -> Self {
+    IdRange { start, end, family: PhantomData }
+  }
+
+  /// Returns the first ID in this range.
+  #[inline]
+  pub const fn start(&self) -> Id<F, R> {
+    Id::from_raw(self.start)
+  }
+
+  /// Returns the ID one past the last ID in this range.
+  #[inline]
+  pub const fn end(&self) -> Id<F, R> {
+    Id::from_raw(self.end)
+  }
+
+  /// Returns `true` if the given ID lies within this range.
+  #[inline]
+  pub fn contains(&self, id: Id<F, R>) -> bool {
+    self.start <= id.id && id.id < self.end
+  }
+
+  /// Returns the number of IDs in this range.
+  #[inline]
+  pub fn len(&self) -> R {
+    self.end.saturating_sub(self.start)
+  }
+
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.start >= self.end
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> Iterator for IdRange<F, R> {
+  type Item = Id<F, R>;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.start < self.end {
+      let id = self.start;
+      self.start = id.saturating_add(R::ONE);
+      Some(Id::from_raw(id))
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    match IdRange::len(self).try_into() {
+      Ok(len) => (len, Some(len)),
+      Err(_) => (usize::MAX, None)
+    }
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> DoubleEndedIterator for IdRange<F, R> {
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.start < self.end {
+      self.end = self.end.saturating_sub(R::ONE);
+      Some(Id::from_raw(self.end))
+    } else {
+      None
+    }
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> std::iter::FusedIterator for IdRange<F, R> {}
+
+impl<F: ?Sized, R: IdRepr> fmt::Debug for IdRange<F, R> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("IdRange")
+      .field("start", &self.start)
+      .field("end", &self.end)
+      .finish()
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> Clone for IdRange<F, R> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> Copy for IdRange<F, R> {}
+
+impl<F: ?Sized, R: IdRepr> PartialEq for IdRange<F, R> {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.start == other.start && self.end == other.end
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> Eq for IdRange<F, R> {}
+
+impl<F: ?Sized, R: IdRepr> Hash for IdRange<F, R> {
+  #[inline]
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.start.hash(state);
+    self.end.hash(state);
+  }
+}
+
+unsafe impl<F: ?Sized, R: IdRepr> Send for IdRange<F, R> {}
+unsafe impl<F: ?Sized, R: IdRepr> Sync for IdRange<F, R> {}
+
+#[cfg(feature = "serde")]
+impl<F: ?Sized, R> serde::Serialize for IdRange<F, R>
+where R: IdRepr + serde::Serialize {
+  #[inline]
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where S: serde::Serializer {
+    (self.start, self.end).serialize(serializer)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, F: ?Sized, R> serde::Deserialize<'de> for IdRange<F, R>
+where R: IdRepr + serde::Deserialize<'de> {
+  #[inline]
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where D: serde::Deserializer<'de> {
+    <(R, R)>::deserialize(deserializer).map(|(start, end)| IdRange::from_raw(start, end))
+  }
+}
+
+
+
+/// An [`Id`] which may never be the maximum raw value,
+/// allowing `Option<NonMaxId>` to be the same size as `NonMaxId`.
+#[repr(transparent)]
+pub struct NonMaxId<F: ?Sized = (), R: IdRepr = RawId> {
+  // stores the bitwise complement of the ID, so that the maximum raw value becomes the zero niche
+  inverted: R::NonZero,
+  family: PhantomData<F>
+}
+
+impl<F: ?Sized, R: IdRepr> NonMaxId<F, R> {
+  /// Creates a new ID from a raw value.
+  /// Panics if the given value is the maximum raw value.
+  #[inline]
+  pub fn from_raw(id: R) -> Self {
+    match NonMaxId::checked(id) {
+      Some(id) => id,
+      None => panic!("non-max id may not be the maximum raw value")
+    }
+  }
+
+  /// Creates a new ID from a raw value, returning `None` if the given value is the maximum raw value.
+  #[inline]
+  pub fn checked(id: R) -> Option<Self> {
+    id.invert_non_zero().map(|inverted| NonMaxId { inverted, family: PhantomData })
+  }
+
+  /// Converts an [`Id`], returning `None` if its raw value is the maximum raw value.
+  #[inline]
+  pub fn from_id(id: Id<F, R>) -> Option<Self> {
+    NonMaxId::checked(id.id)
+  }
+
+  #[inline]
+  pub fn into_raw(self) -> R {
+    R::from_inverted_non_zero(self.inverted)
+  }
+
+  #[inline]
+  pub fn into_id(self) -> Id<F, R> {
+    Id::from_raw(self.into_raw())
+  }
+
+  #[inline]
+  pub const fn cast<U>(self) -> NonMaxId<U, R> {
+    NonMaxId { inverted: self.inverted, family: PhantomData }
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> From<NonMaxId<F, R>> for Id<F, R> {
+  #[inline]
+  fn from(id: NonMaxId<F, R>) -> Self {
+    id.into_id()
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> fmt::Debug for NonMaxId<F, R> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_tuple("NonMaxId")
+      .field(&self.into_raw())
+      .finish()
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> Clone for NonMaxId<F, R> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> Copy for NonMaxId<F, R> {}
+
+impl<F: ?Sized, R: IdRepr> PartialEq for NonMaxId<F, R> {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.inverted == other.inverted
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> Eq for NonMaxId<F, R> {}
+
+impl<F: ?Sized, R: IdRepr> PartialOrd for NonMaxId<F, R> {
+  #[inline]
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(Ord::cmp(self, other))
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> Ord for NonMaxId<F, R> {
+  #[inline]
+  fn cmp(&self, other: &Self) -> Ordering {
+    Ord::cmp(&self.into_raw(), &other.into_raw())
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> Hash for NonMaxId<F, R> {
+  #[inline]
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.into_raw().hash(state);
+  }
+}
+
+unsafe impl<F: ?Sized, R: IdRepr> Send for NonMaxId<F, R> {}
+unsafe impl<F: ?Sized, R: IdRepr> Sync for NonMaxId<F, R> {}
+
+impl<F: ?Sized, R: IdRepr> IsEnabled for NonMaxId<F, R> {}
+
+#[cfg(feature = "serde")]
+impl<F: ?Sized, R> serde::Serialize for NonMaxId<F, R>
+where R: IdRepr + serde::Serialize {
+  #[inline]
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where S: serde::Serializer {
+    self.into_raw().serialize(serializer)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, F: ?Sized, R> serde::Deserialize<'de> for NonMaxId<F, R>
+where R: IdRepr + serde::Deserialize<'de> {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where D: serde::Deserializer<'de> {
+    let id = R::deserialize(deserializer)?;
+    NonMaxId::checked(id).ok_or_else(|| serde::de::Error::invalid_value(
+      serde::de::Unexpected::Unsigned(id.into()),
+      &"an integer less than the maximum raw id"
+    ))
   }
 }
 
@@ -169,39 +622,113 @@ impl<'de, F: ?Sized> serde::Deserialize<'de> for Id<F> {
 /// This is just like `IdContext`, but operates atomically and can be shared between threads.
 #[repr(transparent)]
 #[derive(Debug)]
-pub struct AtomicIdContext<F: ?Sized = ()> {
-  current_id: AtomicU64,
+pub struct AtomicIdContext<F: ?Sized = (), R: IdRepr = RawId> {
+  current_id: R::Atomic,
   family: PhantomData<F>
 }
 
 impl<F: ?Sized> AtomicIdContext<F> {
-  /// Creates a new ID context with an from which it should start counting from.
+  /// Creates a new ID context that will start counting from the given raw ID,
+  /// allowing a sequence to be resumed from a saved counter.
+  ///
+  /// For representations other than [`RawId`], convert from an [`IdContext`] instead.
   #[inline]
-  pub const fn with_current_id(current_id: u64) -> AtomicIdContext<F> {
+  pub const fn with_current_id(current_id: RawId) -> AtomicIdContext<F> {
     AtomicIdContext {
-      current_id: AtomicU64::new(current_id),
+      current_id: std::sync::atomic::AtomicU64::new(current_id),
       family: PhantomData
     }
   }
+}
 
+impl<F: ?Sized, R: IdRepr> AtomicIdContext<F, R> {
   /// Creates a new ID context.
   #[inline]
-  pub const fn new() -> AtomicIdContext<F> {
-    Self::with_current_id(0)
+  pub const fn new() -> AtomicIdContext<F, R> {
+    AtomicIdContext {
+      current_id: R::ATOMIC_ZERO,
+      family: PhantomData
+    }
+  }
+
+  #[inline]
+  fn from_current_id(current_id: R) -> Self {
+    AtomicIdContext { current_id: current_id.atomic_new(), family: PhantomData }
   }
 
-  pub fn next_id(&self) -> Id<F> {
-    let id = self.current_id.fetch_add(1, ORDERING);
+  /// Spawns the next unique ID for this context.
+  ///
+  /// Unlike [`IdContext::next_id`], the counter silently wraps on overflow,
+  /// use [`AtomicIdContext::try_next_id`] if this is a concern.
+  pub fn next_id(&self) -> Id<F, R> {
+    let id = R::atomic_fetch_add(&self.current_id, R::ONE, ORDERING);
+    Id::from_raw(id)
+  }
+
+  /// Spawns the next unique ID for this context using [`Relaxed`][AtomicOrdering::Relaxed] ordering.
+  ///
+  /// IDs spawned this way are still unique, since uniqueness only relies on the atomicity
+  /// of the increment, but no ordering is established with other memory operations.
+  pub fn next_id_relaxed(&self) -> Id<F, R> {
+    let id = R::atomic_fetch_add(&self.current_id, R::ONE, AtomicOrdering::Relaxed);
     Id::from_raw(id)
   }
+
+  /// Spawns the next unique ID for this context, or returns `None` if the context
+  /// has been exhausted (the counter cannot advance past the maximum raw ID).
+  pub fn try_next_id(&self) -> Option<Id<F, R>> {
+    R::atomic_fetch_update(&self.current_id, ORDERING, |id| id.checked_add(R::ONE))
+      .map(Id::from_raw)
+  }
+
+  /// Spawns the given number of unique, contiguous IDs for this context.
+  /// Panics if the context does not have enough IDs left, see [`AtomicIdContext::try_next_ids`].
+  pub fn next_ids(&self, count: R) -> IdRange<F, R> {
+    self.try_next_ids(count).expect("id context exhausted")
+  }
+
+  /// Spawns the given number of unique, contiguous IDs for this context, or returns `None`
+  /// (leaving the context untouched) if the counter cannot advance that far.
+  pub fn try_next_ids(&self, count: R) -> Option<IdRange<F, R>> {
+    let start = R::atomic_fetch_update(&self.current_id, ORDERING, |id| id.checked_add(count))?;
+    Some(IdRange::from_raw(start, start.saturating_add(count)))
+  }
 }
 
-impl<F: ?Sized> Default for AtomicIdContext<F> {
+impl<F: ?Sized, R: IdRepr> Default for AtomicIdContext<F, R> {
   #[inline]
-  fn default() -> AtomicIdContext<F> {
+  fn default() -> AtomicIdContext<F, R> {
     AtomicIdContext::new()
   }
 }
 
-unsafe impl<F: ?Sized> Send for AtomicIdContext<F> {}
-unsafe impl<F: ?Sized> Sync for AtomicIdContext<F> {}
+impl<F: ?Sized, R: IdRepr> From<IdContext<F, R>> for AtomicIdContext<F, R> {
+  #[inline]
+  fn from(context: IdContext<F, R>) -> Self {
+    AtomicIdContext::from_current_id(context.current_id)
+  }
+}
+
+impl<F: ?Sized, R: IdRepr> From<AtomicIdContext<F, R>> for IdContext<F, R> {
+  #[inline]
+  fn from(context: AtomicIdContext<F, R>) -> Self {
+    IdContext::with_current_id(R::atomic_into_inner(context.current_id))
+  }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, F: ?Sized, R> arbitrary::Arbitrary<'a> for AtomicIdContext<F, R>
+where R: IdRepr + arbitrary::Arbitrary<'a> {
+  #[inline]
+  fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+    R::arbitrary(u).map(AtomicIdContext::from_current_id)
+  }
+
+  #[inline]
+  fn size_hint(depth: usize) -> (usize, Option<usize>) {
+    R::size_hint(depth)
+  }
+}
+
+unsafe impl<F: ?Sized, R: IdRepr> Send for AtomicIdContext<F, R> {}
+unsafe impl<F: ?Sized, R: IdRepr> Sync for AtomicIdContext<F, R> {}
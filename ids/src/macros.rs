@@ -0,0 +1,36 @@
+macro_rules! impl_iterator {
+  {
+    $(#[$attr:meta])*
+    $vis:vis struct $Type:ident <$($lt:lifetime),* $(,)? $($gn:ident $(: ?$unsized:ident)?),* $(,)?>,
+    $inner:ident: $InnerType:ty, $Item:ty, $map:expr
+    $(, where $($w:tt)*)? $(,)?
+  } => {
+    $(#[$attr])*
+    $vis struct $Type<$($lt,)* $($gn $(: ?$unsized)?,)*> {
+      $inner: $InnerType
+    }
+
+    impl<$($lt,)* $($gn $(: ?$unsized)?,)*> std::iter::Iterator for $Type<$($lt,)* $($gn,)*> $(where $($w)*)? {
+      type Item = $Item;
+
+      #[inline]
+      fn next(&mut self) -> Option<Self::Item> {
+        self.$inner.next().map($map)
+      }
+
+      #[inline]
+      fn size_hint(&self) -> (usize, Option<usize>) {
+        self.$inner.size_hint()
+      }
+    }
+
+    impl<$($lt,)* $($gn $(: ?$unsized)?,)*> std::iter::ExactSizeIterator for $Type<$($lt,)* $($gn,)*> $(where $($w)*)? {
+      #[inline]
+      fn len(&self) -> usize {
+        self.$inner.len()
+      }
+    }
+
+    impl<$($lt,)* $($gn $(: ?$unsized)?,)*> std::iter::FusedIterator for $Type<$($lt,)* $($gn,)*> $(where $($w)*)? {}
+  };
+}
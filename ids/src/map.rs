@@ -2,7 +2,7 @@ use std::collections::hash_map;
 use std::ops::Index;
 use std::fmt;
 
-use nohash_hasher::{IntMap as NoHashMap, BuildNoHashHasher, IsEnabled};
+use nohash_hasher::{IntMap as NoHashMap, BuildNoHashHasher};
 
 use super::{IdContext, Id};
 
@@ -10,8 +10,6 @@ use super::{IdContext, Id};
 
 pub type IdMapBuildHasher<T> = BuildNoHashHasher<Id<T>>;
 
-impl<F: ?Sized> IsEnabled for Id<F> {}
-
 /// A hashmap with [`Id`]s as opaque keys.
 ///
 /// If you would like an [`IdContext`] to be paired with this, use [`IdMap`] instead.
@@ -44,17 +42,17 @@ impl<T> IdMap<T> {
   }
 
   #[inline]
-  pub fn ids(&self) -> Ids<T> {
+  pub fn ids(&self) -> Ids<'_, T> {
     Ids { inner: self.map.keys() }
   }
 
   #[inline]
-  pub fn values(&self) -> Values<T> {
+  pub fn values(&self) -> Values<'_, T> {
     Values { inner: self.map.values() }
   }
 
   #[inline]
-  pub fn values_mut(&mut self) -> ValuesMut<T> {
+  pub fn values_mut(&mut self) -> ValuesMut<'_, T> {
     ValuesMut { inner: self.map.values_mut() }
   }
 
@@ -64,12 +62,12 @@ impl<T> IdMap<T> {
   }
 
   #[inline]
-  pub fn iter(&self) -> Iter<T> {
+  pub fn iter(&self) -> Iter<'_, T> {
     self.into_iter()
   }
 
   #[inline]
-  pub fn iter_mut(&mut self) -> IterMut<T> {
+  pub fn iter_mut(&mut self) -> IterMut<'_, T> {
     self.into_iter()
   }
 
@@ -84,7 +82,7 @@ impl<T> IdMap<T> {
   }
 
   #[inline]
-  pub fn drain(&mut self) -> Drain<T> {
+  pub fn drain(&mut self) -> Drain<'_, T> {
     self.context.current_id = 0;
     Drain { inner: self.map.drain() }
   }
@@ -147,6 +145,16 @@ impl<T> IdMap<T> {
     id
   }
 
+  /// Advances the map's [`IdContext`] past the given ID, so that later calls to
+  /// [`IdMap::insert_new`] will not collide with it. Has no effect if it is already past it.
+  ///
+  /// If the given ID is the maximum raw ID, the context is left exhausted,
+  /// and later calls to [`IdMap::insert_new`] will panic.
+  #[inline]
+  pub fn bump_context_to(&mut self, at_least: Id<T>) {
+    self.context.current_id = self.context.current_id.max(at_least.id.saturating_add(1));
+  }
+
   #[inline]
   pub fn insert(&mut self, id: Id<T>, value: T) -> Option<T> {
     self.map.insert(id, value)
@@ -177,6 +185,27 @@ impl<T> Default for IdMap<T> {
   }
 }
 
+impl<T> Extend<(Id<T>, T)> for IdMap<T> {
+  /// Inserts every entry from the iterator, advancing the map's [`IdContext`]
+  /// past the highest ID seen so that later calls to [`IdMap::insert_new`] do not collide.
+  fn extend<I: IntoIterator<Item = (Id<T>, T)>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.map.reserve(iter.size_hint().0);
+    for (id, value) in iter {
+      self.bump_context_to(id);
+      self.map.insert(id, value);
+    };
+  }
+}
+
+impl<T> FromIterator<(Id<T>, T)> for IdMap<T> {
+  fn from_iter<I: IntoIterator<Item = (Id<T>, T)>>(iter: I) -> Self {
+    let mut map = IdMap::new();
+    map.extend(iter);
+    map
+  }
+}
+
 impl<T> Index<Id<T>> for IdMap<T> {
   type Output = T;
 
@@ -246,7 +275,7 @@ where T: serde::Deserialize<'de> {
       where A: serde::de::MapAccess<'de> {
         let size = map.size_hint().unwrap_or(0).min(4096);
         let mut values = NoHashMap::with_capacity_and_hasher(size, Default::default());
-        let mut current_id: u64 = 0;
+        let mut current_id: super::RawId = 0;
 
         while let Some((key, value)) = map.next_entry::<Id<T>, T>()? {
           current_id = current_id.max(key.id);
@@ -266,43 +295,6 @@ where T: serde::Deserialize<'de> {
   }
 }
 
-macro_rules! impl_iterator {
-  {
-    $(#[$attr:meta])*
-    $vis:vis struct $Type:ident <$($lt:lifetime),* $(,)? $($gn:ident),* $(,)?>,
-    $inner:ident: $InnerType:ty, $Item:ty, $map:expr
-    $(, where $($w:tt)*)? $(,)?
-  } => {
-    $(#[$attr])*
-    $vis struct $Type<$($lt,)* $($gn,)*> {
-      $inner: $InnerType
-    }
-
-    impl<$($lt,)* $($gn,)*> std::iter::Iterator for $Type<$($lt,)* $($gn,)*> $(where $($w)*)? {
-      type Item = $Item;
-
-      #[inline]
-      fn next(&mut self) -> Option<Self::Item> {
-        self.$inner.next().map($map)
-      }
-
-      #[inline]
-      fn size_hint(&self) -> (usize, Option<usize>) {
-        self.$inner.size_hint()
-      }
-    }
-
-    impl<$($lt,)* $($gn,)*> std::iter::ExactSizeIterator for $Type<$($lt,)* $($gn,)*> $(where $($w)*)? {
-      #[inline]
-      fn len(&self) -> usize {
-        self.$inner.len()
-      }
-    }
-
-    impl<$($lt,)* $($gn,)*> std::iter::FusedIterator for $Type<$($lt,)* $($gn,)*> $(where $($w)*)? {}
-  };
-}
-
 impl_iterator! {
   #[derive(Debug, Clone)] pub struct Ids<'a, T>,
   inner: hash_map::Keys<'a, Id<T>, T>,
@@ -0,0 +1,122 @@
+//! The integer types that can back an [`Id`](crate::Id).
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::num::{NonZeroU32, NonZeroU64, ParseIntError};
+use std::hash::Hash;
+use std::str::FromStr;
+use std::fmt;
+
+mod sealed {
+  pub trait Sealed {}
+}
+
+
+
+/// An unsigned integer type that can back an [`Id`](crate::Id) and the types built on it.
+///
+/// This is implemented for `u64` (the default) and `u32`.
+/// It is sealed, and cannot be implemented outside of this crate.
+pub trait IdRepr: sealed::Sealed
+  + Copy + Ord + Hash + fmt::Debug + fmt::Display
+  + FromStr<Err = ParseIntError> + Into<u64> + TryInto<usize>
+  + Send + Sync + 'static
+{
+  /// The atomic counterpart of this type, used by [`AtomicIdContext`](crate::AtomicIdContext).
+  type Atomic: fmt::Debug + Send + Sync;
+  /// The non-zero counterpart of this type, used by [`NonMaxId`](crate::NonMaxId).
+  type NonZero: Copy + Eq;
+
+  const ZERO: Self;
+  const ONE: Self;
+  const MAX: Self;
+
+  fn checked_add(self, rhs: Self) -> Option<Self>;
+  fn saturating_add(self, rhs: Self) -> Self;
+  fn saturating_sub(self, rhs: Self) -> Self;
+
+  #[doc(hidden)]
+  fn invert_non_zero(self) -> Option<Self::NonZero>;
+  #[doc(hidden)]
+  fn from_inverted_non_zero(inverted: Self::NonZero) -> Self;
+
+  #[doc(hidden)]
+  const ATOMIC_ZERO: Self::Atomic;
+  #[doc(hidden)]
+  fn atomic_new(self) -> Self::Atomic;
+  #[doc(hidden)]
+  fn atomic_into_inner(atomic: Self::Atomic) -> Self;
+  #[doc(hidden)]
+  fn atomic_fetch_add(atomic: &Self::Atomic, value: Self, ordering: Ordering) -> Self;
+  #[doc(hidden)]
+  fn atomic_fetch_update<U>(atomic: &Self::Atomic, ordering: Ordering, update: U) -> Option<Self>
+  where U: FnMut(Self) -> Option<Self>;
+}
+
+macro_rules! impl_id_repr {
+  ($($Raw:ident => $Atomic:ident, $NonZero:ident);* $(;)?) => {$(
+    impl sealed::Sealed for $Raw {}
+
+    impl IdRepr for $Raw {
+      type Atomic = $Atomic;
+      type NonZero = $NonZero;
+
+      const ZERO: Self = 0;
+      const ONE: Self = 1;
+      const MAX: Self = $Raw::MAX;
+      // only ever used by value, to initialize a fresh counter in `AtomicIdContext::new`
+      #[allow(clippy::declare_interior_mutable_const)]
+      const ATOMIC_ZERO: Self::Atomic = $Atomic::new(0);
+
+      #[inline]
+      fn checked_add(self, rhs: Self) -> Option<Self> {
+        $Raw::checked_add(self, rhs)
+      }
+
+      #[inline]
+      fn saturating_add(self, rhs: Self) -> Self {
+        $Raw::saturating_add(self, rhs)
+      }
+
+      #[inline]
+      fn saturating_sub(self, rhs: Self) -> Self {
+        $Raw::saturating_sub(self, rhs)
+      }
+
+      #[inline]
+      fn invert_non_zero(self) -> Option<Self::NonZero> {
+        $NonZero::new(!self)
+      }
+
+      #[inline]
+      fn from_inverted_non_zero(inverted: Self::NonZero) -> Self {
+        !inverted.get()
+      }
+
+      #[inline]
+      fn atomic_new(self) -> Self::Atomic {
+        $Atomic::new(self)
+      }
+
+      #[inline]
+      fn atomic_into_inner(atomic: Self::Atomic) -> Self {
+        atomic.into_inner()
+      }
+
+      #[inline]
+      fn atomic_fetch_add(atomic: &Self::Atomic, value: Self, ordering: Ordering) -> Self {
+        atomic.fetch_add(value, ordering)
+      }
+
+      #[inline]
+      fn atomic_fetch_update<U>(atomic: &Self::Atomic, ordering: Ordering, update: U) -> Option<Self>
+      where U: FnMut(Self) -> Option<Self> {
+        atomic.fetch_update(ordering, ordering, update).ok()
+      }
+    }
+  )*};
+}
+
+impl_id_repr! {
+  u64 => AtomicU64, NonZeroU64;
+  u32 => AtomicU32, NonZeroU32;
+}
@@ -0,0 +1,273 @@
+use std::collections::hash_set;
+use std::fmt;
+
+use nohash_hasher::{IntSet as NoHashSet, BuildNoHashHasher};
+
+use super::Id;
+
+
+
+pub type IdSetBuildHasher<F> = BuildNoHashHasher<Id<F>>;
+
+/// A hashset of [`Id`]s.
+pub struct IdSet<F: ?Sized> {
+  set: NoHashSet<Id<F>>
+}
+
+impl<F: ?Sized> IdSet<F> {
+  pub fn new() -> Self {
+    IdSet::from_set_raw(NoHashSet::with_hasher(Default::default()))
+  }
+
+  pub fn with_capacity(capacity: usize) -> Self {
+    IdSet::from_set_raw(NoHashSet::with_capacity_and_hasher(capacity, Default::default()))
+  }
+
+  #[inline]
+  fn from_set_raw(set: NoHashSet<Id<F>>) -> Self {
+    IdSet { set }
+  }
+
+  #[inline]
+  pub fn into_inner(self) -> NoHashSet<Id<F>> {
+    self.set
+  }
+
+  #[inline]
+  pub fn as_inner(&self) -> &NoHashSet<Id<F>> {
+    &self.set
+  }
+
+  #[inline]
+  pub fn capacity(&self) -> usize {
+    self.set.capacity()
+  }
+
+  #[inline]
+  pub fn iter(&self) -> Iter<'_, F> {
+    self.into_iter()
+  }
+
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.set.len()
+  }
+
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.set.is_empty()
+  }
+
+  #[inline]
+  pub fn drain(&mut self) -> Drain<'_, F> {
+    Drain { inner: self.set.drain() }
+  }
+
+  #[inline]
+  pub fn clear(&mut self) {
+    self.set.clear();
+  }
+
+  #[inline]
+  pub fn reserve(&mut self, additional: usize) {
+    self.set.reserve(additional);
+  }
+
+  #[inline]
+  pub fn shrink_to_fit(&mut self) {
+    self.set.shrink_to_fit()
+  }
+
+  #[inline]
+  pub fn contains(&self, id: Id<F>) -> bool {
+    self.set.contains(&id)
+  }
+
+  /// Adds an ID to the set, returning `false` if it was already present.
+  #[inline]
+  pub fn insert(&mut self, id: Id<F>) -> bool {
+    self.set.insert(id)
+  }
+
+  /// Removes an ID from the set, returning `false` if it was not present.
+  #[inline]
+  pub fn remove(&mut self, id: Id<F>) -> bool {
+    self.set.remove(&id)
+  }
+
+  #[inline]
+  pub fn retain<P>(&mut self, mut f: P)
+  where P: FnMut(Id<F>) -> bool {
+    self.set.retain(move |&id| f(id));
+  }
+
+  /// Returns an iterator over the IDs in either set, without duplicates.
+  #[inline]
+  pub fn union<'a>(&'a self, other: &'a IdSet<F>) -> impl Iterator<Item = Id<F>> + 'a {
+    self.set.union(&other.set).copied()
+  }
+
+  /// Returns an iterator over the IDs in both sets.
+  #[inline]
+  pub fn intersection<'a>(&'a self, other: &'a IdSet<F>) -> impl Iterator<Item = Id<F>> + 'a {
+    self.set.intersection(&other.set).copied()
+  }
+
+  /// Returns an iterator over the IDs in this set but not in `other`.
+  #[inline]
+  pub fn difference<'a>(&'a self, other: &'a IdSet<F>) -> impl Iterator<Item = Id<F>> + 'a {
+    self.set.difference(&other.set).copied()
+  }
+
+  /// Returns an iterator over the IDs in exactly one of the two sets.
+  #[inline]
+  pub fn symmetric_difference<'a>(&'a self, other: &'a IdSet<F>) -> impl Iterator<Item = Id<F>> + 'a {
+    self.set.symmetric_difference(&other.set).copied()
+  }
+
+  #[inline]
+  pub fn is_disjoint(&self, other: &IdSet<F>) -> bool {
+    self.set.is_disjoint(&other.set)
+  }
+
+  #[inline]
+  pub fn is_subset(&self, other: &IdSet<F>) -> bool {
+    self.set.is_subset(&other.set)
+  }
+
+  #[inline]
+  pub fn is_superset(&self, other: &IdSet<F>) -> bool {
+    self.set.is_superset(&other.set)
+  }
+}
+
+impl<F: ?Sized> fmt::Debug for IdSet<F> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_set().entries(self.iter()).finish()
+  }
+}
+
+impl<F: ?Sized> Clone for IdSet<F> {
+  #[inline]
+  fn clone(&self) -> Self {
+    IdSet::from_set_raw(self.set.clone())
+  }
+}
+
+impl<F: ?Sized> Default for IdSet<F> {
+  #[inline]
+  fn default() -> Self {
+    IdSet::new()
+  }
+}
+
+impl<F: ?Sized> PartialEq for IdSet<F> {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.set == other.set
+  }
+}
+
+impl<F: ?Sized> Eq for IdSet<F> {}
+
+impl<F: ?Sized> From<NoHashSet<Id<F>>> for IdSet<F> {
+  #[inline]
+  fn from(set: NoHashSet<Id<F>>) -> Self {
+    IdSet::from_set_raw(set)
+  }
+}
+
+impl<F: ?Sized> Extend<Id<F>> for IdSet<F> {
+  #[inline]
+  fn extend<I: IntoIterator<Item = Id<F>>>(&mut self, iter: I) {
+    self.set.extend(iter);
+  }
+}
+
+impl<F: ?Sized> FromIterator<Id<F>> for IdSet<F> {
+  #[inline]
+  fn from_iter<I: IntoIterator<Item = Id<F>>>(iter: I) -> Self {
+    IdSet::from_set_raw(iter.into_iter().collect())
+  }
+}
+
+impl<'a, F: ?Sized> IntoIterator for &'a IdSet<F> {
+  type Item = Id<F>;
+  type IntoIter = Iter<'a, F>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    Iter { inner: self.set.iter() }
+  }
+}
+
+impl<F: ?Sized> IntoIterator for IdSet<F> {
+  type Item = Id<F>;
+  type IntoIter = IntoIter<F>;
+
+  #[inline]
+  fn into_iter(self) -> Self::IntoIter {
+    IntoIter { inner: self.set.into_iter() }
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<F: ?Sized> serde::Serialize for IdSet<F> {
+  #[inline]
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where S: serde::Serializer {
+    self.set.serialize(serializer)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, F: ?Sized> serde::Deserialize<'de> for IdSet<F> {
+  #[inline]
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where D: serde::Deserializer<'de> {
+    NoHashSet::deserialize(deserializer).map(IdSet::from_set_raw)
+  }
+}
+
+impl_iterator! {
+  pub struct Iter<'a, F: ?Sized>,
+  inner: hash_set::Iter<'a, Id<F>>,
+  Id<F>, |id| *id
+}
+
+impl<'a, F: ?Sized> fmt::Debug for Iter<'a, F> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Iter").field("inner", &self.inner).finish()
+  }
+}
+
+impl<'a, F: ?Sized> Clone for Iter<'a, F> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Iter { inner: self.inner.clone() }
+  }
+}
+
+impl_iterator! {
+  pub struct IntoIter<F: ?Sized>,
+  inner: hash_set::IntoIter<Id<F>>,
+  Id<F>, std::convert::identity
+}
+
+impl<F: ?Sized> fmt::Debug for IntoIter<F> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("IntoIter").field("inner", &self.inner).finish()
+  }
+}
+
+impl_iterator! {
+  pub struct Drain<'a, F: ?Sized>,
+  inner: hash_set::Drain<'a, Id<F>>,
+  Id<F>, std::convert::identity
+}
+
+impl<'a, F: ?Sized> fmt::Debug for Drain<'a, F> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Drain").field("inner", &self.inner).finish()
+  }
+}
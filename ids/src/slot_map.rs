@@ -0,0 +1,209 @@
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::fmt;
+
+use super::RawId;
+
+
+
+/// An ID consisting of a slot index and a generation, as handed out by an [`IdSlotMap`].
+/// Once the value in a slot has been removed, IDs with older generations no longer refer to it.
+pub struct GenId<F: ?Sized = ()> {
+  index: RawId,
+  generation: RawId,
+  family: PhantomData<F>
+}
+
+impl<F: ?Sized> GenId<F> {
+  #[inline]
+  pub const fn from_raw(index: RawId, generation: RawId) -> Self {
+    GenId { index, generation, family: PhantomData }
+  }
+
+  #[inline]
+  pub const fn index(self) -> RawId {
+    self.index
+  }
+
+  #[inline]
+  pub const fn generation(self) -> RawId {
+    self.generation
+  }
+
+  #[inline]
+  pub const fn cast<U>(self) -> GenId<U> {
+    GenId::from_raw(self.index, self.generation)
+  }
+}
+
+impl<F: ?Sized> fmt::Debug for GenId<F> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_tuple("GenId")
+      .field(&self.index)
+      .field(&self.generation)
+      .finish()
+  }
+}
+
+impl<F: ?Sized> Clone for GenId<F> {
+  #[inline]
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<F: ?Sized> Copy for GenId<F> {}
+
+impl<F: ?Sized> PartialEq for GenId<F> {
+  #[inline]
+  fn eq(&self, other: &Self) -> bool {
+    self.index == other.index && self.generation == other.generation
+  }
+}
+
+impl<F: ?Sized> Eq for GenId<F> {}
+
+impl<F: ?Sized> Hash for GenId<F> {
+  #[inline]
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.index.hash(state);
+    self.generation.hash(state);
+  }
+}
+
+unsafe impl<F: ?Sized> Send for GenId<F> {}
+unsafe impl<F: ?Sized> Sync for GenId<F> {}
+
+
+
+#[derive(Debug, Clone)]
+struct Slot<T> {
+  generation: RawId,
+  value: Option<T>
+}
+
+/// A map that assigns a [`GenId`] to each inserted value, reusing the slots of removed values.
+/// Removing a value bumps the generation of its slot, so stale IDs will not match a reused slot.
+pub struct IdSlotMap<F: ?Sized, T> {
+  slots: Vec<Slot<T>>,
+  free: Vec<usize>,
+  len: usize,
+  family: PhantomData<F>
+}
+
+impl<F: ?Sized, T> IdSlotMap<F, T> {
+  #[inline]
+  pub const fn new() -> Self {
+    IdSlotMap { slots: Vec::new(), free: Vec::new(), len: 0, family: PhantomData }
+  }
+
+  #[inline]
+  pub fn with_capacity(capacity: usize) -> Self {
+    IdSlotMap { slots: Vec::with_capacity(capacity), free: Vec::new(), len: 0, family: PhantomData }
+  }
+
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Inserts a value into a free slot, returning its ID.
+  pub fn insert(&mut self, value: T) -> GenId<F> {
+    self.len += 1;
+    match self.free.pop() {
+      Some(index) => {
+        let slot = &mut self.slots[index];
+        slot.value = Some(value);
+        GenId::from_raw(index as RawId, slot.generation)
+      },
+      None => {
+        self.slots.push(Slot { generation: 0, value: Some(value) });
+        GenId::from_raw((self.slots.len() - 1) as RawId, 0)
+      }
+    }
+  }
+
+  /// Removes the value with the given ID, returning it if the ID was not stale.
+  pub fn remove(&mut self, id: GenId<F>) -> Option<T> {
+    let index = id.index as usize;
+    let slot = self.slots.get_mut(index)?;
+    if slot.generation != id.generation { return None };
+    let value = slot.value.take()?;
+    slot.generation = slot.generation.wrapping_add(1);
+    self.free.push(index);
+    self.len -= 1;
+    Some(value)
+  }
+
+  #[inline]
+  pub fn contains(&self, id: GenId<F>) -> bool {
+    self.get(id).is_some()
+  }
+
+  /// Gets a reference to the value with the given ID, returning `None` if the ID is stale.
+  pub fn get(&self, id: GenId<F>) -> Option<&T> {
+    self.slots.get(id.index as usize)
+      .filter(|slot| slot.generation == id.generation)
+      .and_then(|slot| slot.value.as_ref())
+  }
+
+  /// Gets a mutable reference to the value with the given ID, returning `None` if the ID is stale.
+  pub fn get_mut(&mut self, id: GenId<F>) -> Option<&mut T> {
+    self.slots.get_mut(id.index as usize)
+      .filter(|slot| slot.generation == id.generation)
+      .and_then(|slot| slot.value.as_mut())
+  }
+
+  /// Returns an iterator over the IDs and values in this map, in slot order.
+  pub fn iter(&self) -> impl Iterator<Item = (GenId<F>, &T)> + '_ {
+    self.slots.iter().enumerate().filter_map(|(index, slot)| {
+      slot.value.as_ref().map(|value| (GenId::from_raw(index as RawId, slot.generation), value))
+    })
+  }
+
+  /// Removes all values, invalidating every ID handed out so far.
+  pub fn clear(&mut self) {
+    self.free.clear();
+    for (index, slot) in self.slots.iter_mut().enumerate() {
+      if slot.value.take().is_some() {
+        slot.generation = slot.generation.wrapping_add(1);
+      };
+
+      self.free.push(index);
+    };
+
+    self.len = 0;
+  }
+}
+
+impl<F: ?Sized, T: fmt::Debug> fmt::Debug for IdSlotMap<F, T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<F: ?Sized, T: Clone> Clone for IdSlotMap<F, T> {
+  fn clone(&self) -> Self {
+    IdSlotMap {
+      slots: self.slots.clone(),
+      free: self.free.clone(),
+      len: self.len,
+      family: PhantomData
+    }
+  }
+}
+
+impl<F: ?Sized, T> Default for IdSlotMap<F, T> {
+  #[inline]
+  fn default() -> Self {
+    IdSlotMap::new()
+  }
+}
+
+unsafe impl<F: ?Sized, T: Send> Send for IdSlotMap<F, T> {}
+unsafe impl<F: ?Sized, T: Sync> Sync for IdSlotMap<F, T> {}
@@ -0,0 +1,141 @@
+//! Serialization of [`Id`]s alongside the name of their family, for use with
+//! `#[serde(with = "ids::tagged")]`. Deserializing an ID tagged with a different
+//! family name than the one expected results in an error.
+
+use crate::{Id, IdFamily, IdRepr};
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::fmt;
+
+const FIELDS: &[&str] = &["family", "id"];
+
+
+
+/// Serializes an [`Id`] as a struct containing its family name and raw value.
+pub fn serialize<F, R, S>(id: &Id<F, R>, serializer: S) -> Result<S::Ok, S::Error>
+where F: ?Sized + IdFamily, R: IdRepr + serde::Serialize, S: serde::Serializer {
+  let mut state = serializer.serialize_struct("Id", 2)?;
+  serde::ser::SerializeStruct::serialize_field(&mut state, "family", F::NAME)?;
+  serde::ser::SerializeStruct::serialize_field(&mut state, "id", &id.into_raw())?;
+  serde::ser::SerializeStruct::end(state)
+}
+
+/// Deserializes an [`Id`] serialized by [`serialize`],
+/// failing if its family name does not match the expected family.
+pub fn deserialize<'de, F, R, D>(deserializer: D) -> Result<Id<F, R>, D::Error>
+where F: ?Sized + IdFamily, R: IdRepr + serde::Deserialize<'de>, D: serde::Deserializer<'de> {
+  deserializer.deserialize_struct("Id", FIELDS, TaggedIdVisitor { id: PhantomData })
+}
+
+fn check_family<F, E>(family: &str) -> Result<(), E>
+where F: ?Sized + IdFamily, E: serde::de::Error {
+  if family == F::NAME {
+    Ok(())
+  } else {
+    Err(E::invalid_value(serde::de::Unexpected::Str(family), &F::NAME))
+  }
+}
+
+enum TaggedIdField {
+  Family,
+  Id
+}
+
+impl<'de> serde::Deserialize<'de> for TaggedIdField {
+  #[inline]
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where D: serde::Deserializer<'de> {
+    deserializer.deserialize_identifier(TaggedIdFieldVisitor)
+  }
+}
+
+struct TaggedIdFieldVisitor;
+
+impl<'de> serde::de::Visitor<'de> for TaggedIdFieldVisitor {
+  type Value = TaggedIdField;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("field identifier")
+  }
+
+  fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+  where E: serde::de::Error {
+    match value {
+      0u64 => Ok(TaggedIdField::Family),
+      1u64 => Ok(TaggedIdField::Id),
+      _ => Err(serde::de::Error::invalid_value(
+        serde::de::Unexpected::Unsigned(value),
+        &"field index 0 <= i < 2"
+      ))
+    }
+  }
+
+  fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+  where E: serde::de::Error {
+    match value {
+      "family" => Ok(TaggedIdField::Family),
+      "id" => Ok(TaggedIdField::Id),
+      _ => Err(serde::de::Error::unknown_field(value, FIELDS))
+    }
+  }
+
+  fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+  where E: serde::de::Error {
+    match value {
+      b"family" => Ok(TaggedIdField::Family),
+      b"id" => Ok(TaggedIdField::Id),
+      _ => {
+        let value = String::from_utf8_lossy(value);
+        Err(serde::de::Error::unknown_field(&value, FIELDS))
+      }
+    }
+  }
+}
+
+struct TaggedIdVisitor<F: ?Sized, R: IdRepr> {
+  id: PhantomData<Id<F, R>>
+}
+
+impl<'de, F, R> serde::de::Visitor<'de> for TaggedIdVisitor<F, R>
+where F: ?Sized + IdFamily, R: IdRepr + serde::Deserialize<'de> {
+  type Value = Id<F, R>;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "struct Id tagged with family {:?}", F::NAME)
+  }
+
+  fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+  where A: serde::de::SeqAccess<'de> {
+    let family = seq.next_element::<Cow<'de, str>>()?
+      .ok_or(serde::de::Error::invalid_length(0usize, &"struct Id with 2 elements"))?;
+    check_family::<F, A::Error>(&family)?;
+    let id = seq.next_element::<R>()?
+      .ok_or(serde::de::Error::invalid_length(1usize, &"struct Id with 2 elements"))?;
+    Ok(Id::from_raw(id))
+  }
+
+  fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+  where A: serde::de::MapAccess<'de> {
+    let mut family = None;
+    let mut id = None;
+    while let Some(key) = map.next_key::<TaggedIdField>()? {
+      match key {
+        TaggedIdField::Family => {
+          if family.is_some() { return Err(serde::de::Error::duplicate_field("family")) };
+          let value = map.next_value::<Cow<'de, str>>()?;
+          check_family::<F, A::Error>(&value)?;
+          family = Some(value);
+        },
+        TaggedIdField::Id => {
+          if id.is_some() { return Err(serde::de::Error::duplicate_field("id")) };
+          id = Some(map.next_value::<R>()?);
+        }
+      };
+    };
+
+    family.ok_or(serde::de::Error::missing_field("family"))?;
+    let id = id.ok_or(serde::de::Error::missing_field("id"))?;
+    Ok(Id::from_raw(id))
+  }
+}
@@ -0,0 +1,353 @@
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+extern crate ids;
+#[cfg(feature = "serde")]
+extern crate ron;
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(feature = "map")]
+use ids::IdMap;
+use ids::{AtomicIdContext, GenId, Id, IdContext, IdRange, IdSet, IdSlotMap, NonMaxId, RawId};
+
+use std::collections::HashSet;
+use std::mem::size_of;
+use std::sync::Arc;
+use std::thread;
+
+#[cfg(feature = "map")]
+#[test]
+fn id_map_extend() {
+  let mut map = [(Id::from_raw(3), "three"), (Id::from_raw(7), "seven")]
+    .into_iter().collect::<IdMap<&str>>();
+  assert_eq!(map.len(), 2);
+
+  let id = map.insert_new("eight");
+  assert_eq!(id, Id::from_raw(8));
+
+  map.extend([(Id::from_raw(0), "zero"), (Id::from_raw(12), "twelve")]);
+  let id = map.insert_new("thirteen");
+  assert_eq!(id, Id::from_raw(13));
+
+  // entries below the current ID must not rewind the context
+  map.extend([(Id::from_raw(1), "one")]);
+  let id = map.insert_new("fourteen");
+  assert_eq!(id, Id::from_raw(14));
+  assert_eq!(map.len(), 8);
+  assert_eq!(map[Id::from_raw(7)], "seven");
+}
+
+#[test]
+fn non_max_id() {
+  assert_eq!(size_of::<Option<NonMaxId>>(), 8);
+  assert_eq!(size_of::<Option<NonMaxId>>(), size_of::<Id>());
+
+  assert!(NonMaxId::<()>::checked(RawId::MAX).is_none());
+  assert!(NonMaxId::<()>::from_id(Id::from_raw(RawId::MAX)).is_none());
+  for raw in [0, 1, 42, RawId::MAX - 1] {
+    let id = NonMaxId::<()>::from_raw(raw);
+    assert_eq!(id.into_raw(), raw);
+    assert_eq!(Id::from(id), Id::from_raw(raw));
+  };
+
+  assert!(NonMaxId::<()>::from_raw(1) < NonMaxId::from_raw(2));
+}
+
+#[test]
+fn id_range() {
+  let mut context = IdContext::<()>::with_current_id(10);
+  let range = context.next_ids(4);
+  assert_eq!(range, IdRange::new(Id::from_raw(10), Id::from_raw(14)));
+  assert_eq!(context.next_id(), Id::from_raw(14));
+
+  assert_eq!(range.len(), 4);
+  assert!(!range.contains(Id::from_raw(9)));
+  assert!(range.contains(Id::from_raw(10)));
+  assert!(range.contains(Id::from_raw(13)));
+  assert!(!range.contains(Id::from_raw(14)));
+
+  let ids = range.map(Id::into_raw).collect::<Vec<RawId>>();
+  assert_eq!(ids, [10, 11, 12, 13]);
+  let mut range = range;
+  assert_eq!(range.next_back(), Some(Id::from_raw(13)));
+  assert_eq!(range.len(), 3);
+
+  let empty = IdRange::<()>::from_raw(5, 5);
+  assert!(empty.is_empty());
+  assert_eq!(empty.count(), 0);
+}
+
+#[test]
+fn try_next_ids() {
+  let mut context = IdContext::<()>::with_current_id(RawId::MAX - 4);
+  assert_eq!(context.try_next_ids(5), None);
+  assert_eq!(context.current_id(), RawId::MAX - 4);
+  assert_eq!(context.try_next_ids(4), Some(IdRange::from_raw(RawId::MAX - 4, RawId::MAX)));
+  assert_eq!(context.try_next_ids(1), None);
+
+  let context = AtomicIdContext::<()>::with_current_id(RawId::MAX - 4);
+  assert_eq!(context.try_next_ids(5), None);
+  assert_eq!(context.try_next_ids(4), Some(IdRange::from_raw(RawId::MAX - 4, RawId::MAX)));
+  assert_eq!(IdContext::from(context).current_id(), RawId::MAX);
+}
+
+#[test]
+fn cast_checked() {
+  struct Player;
+  struct Enemy;
+
+  let id = Id::<Player>::from_raw(7);
+  let accepted = id.cast_checked::<Enemy>(|raw| raw < 10);
+  assert_eq!(accepted, Some(Id::<Enemy>::from_raw(7)));
+  let rejected = id.cast_checked::<Enemy>(|raw| raw >= 10);
+  assert_eq!(rejected, None);
+}
+
+#[test]
+fn u32_repr() {
+  assert_eq!(size_of::<Id<(), u32>>(), 4);
+  assert_eq!(size_of::<Option<NonMaxId<(), u32>>>(), 4);
+  assert_eq!(size_of::<IdRange<(), u32>>(), 8);
+  assert_eq!(size_of::<Id>(), 8);
+
+  let mut context = IdContext::<(), u32>::with_current_id(u32::MAX - 1);
+  let id: u32 = context.next_id().into_raw();
+  assert_eq!(id, u32::MAX - 1);
+  assert_eq!(context.try_next_id(), None);
+
+  static ATOMIC_CONTEXT: AtomicIdContext<(), u32> = AtomicIdContext::new();
+  assert_eq!(ATOMIC_CONTEXT.next_ids(3), IdRange::from_raw(0, 3));
+  assert_eq!(NonMaxId::<(), u32>::checked(u32::MAX), None);
+}
+
+#[test]
+fn id_set() {
+  fn set(raw: &[RawId]) -> IdSet<()> {
+    raw.iter().copied().map(Id::from_raw).collect()
+  }
+
+  fn sorted(iter: impl Iterator<Item = Id<()>>) -> Vec<RawId> {
+    let mut raw = iter.map(Id::into_raw).collect::<Vec<RawId>>();
+    raw.sort_unstable();
+    raw
+  }
+
+  let mut a = set(&[1, 2, 3]);
+  let b = set(&[3, 4]);
+  assert_eq!(sorted(a.union(&b)), [1, 2, 3, 4]);
+  assert_eq!(sorted(a.intersection(&b)), [3]);
+  assert_eq!(sorted(a.difference(&b)), [1, 2]);
+  assert_eq!(sorted(a.symmetric_difference(&b)), [1, 2, 4]);
+
+  assert!(a.insert(Id::from_raw(5)));
+  assert!(!a.insert(Id::from_raw(5)));
+  assert!(a.remove(Id::from_raw(3)));
+  assert!(!a.contains(Id::from_raw(3)));
+  assert!(a.is_disjoint(&b));
+  assert_eq!(sorted(a.iter()), [1, 2, 5]);
+}
+
+#[test]
+fn id_set_unsized_family() {
+  let mut set = IdSet::<str>::new();
+  assert!(set.insert(Id::from_raw(1)));
+  assert!(!set.insert(Id::from_raw(1)));
+  assert_eq!(set.iter().collect::<Vec<Id<str>>>(), [Id::from_raw(1)]);
+}
+
+#[test]
+fn atomic_context_conversion() {
+  let mut context = IdContext::<()>::new();
+  context.next_ids(5);
+
+  let atomic = AtomicIdContext::from(context);
+  assert_eq!(atomic.next_id(), Id::from_raw(5));
+
+  let mut context = IdContext::from(atomic);
+  assert_eq!(context.current_id(), 6);
+  assert_eq!(context.next_id(), Id::from_raw(6));
+}
+
+#[test]
+fn try_next_id() {
+  let mut context = IdContext::<()>::with_current_id(RawId::MAX - 1);
+  assert_eq!(context.try_next_id(), Some(Id::from_raw(RawId::MAX - 1)));
+  assert_eq!(context.try_next_id(), None);
+  assert_eq!(context.current_id(), RawId::MAX);
+
+  let context = AtomicIdContext::<()>::with_current_id(RawId::MAX - 1);
+  assert_eq!(context.try_next_id(), Some(Id::from_raw(RawId::MAX - 1)));
+  assert_eq!(context.try_next_id(), None);
+  assert_eq!(IdContext::from(context).current_id(), RawId::MAX);
+}
+
+#[test]
+fn with_current_id() {
+  const CONTEXT: IdContext<()> = IdContext::with_current_id(42);
+  let mut context = CONTEXT;
+  assert_eq!(context.next_id(), Id::from_raw(42));
+  assert_eq!(context.current_id(), 43);
+
+  let context = AtomicIdContext::<()>::with_current_id(42);
+  assert_eq!(context.next_id(), Id::from_raw(42));
+  assert_eq!(context.next_id(), Id::from_raw(43));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn tagged_serde() {
+  use ids::IdFamily;
+
+  enum Player {}
+
+  impl IdFamily for Player {
+    const NAME: &'static str = "Player";
+  }
+
+  enum Item {}
+
+  impl IdFamily for Item {
+    const NAME: &'static str = "Item";
+  }
+
+  #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+  struct Save {
+    #[serde(with = "ids::tagged")]
+    player: Id<Player>
+  }
+
+  #[derive(Debug, serde::Deserialize)]
+  struct ItemSave {
+    #[serde(with = "ids::tagged")]
+    #[allow(dead_code)]
+    player: Id<Item>
+  }
+
+  let save = Save { player: Id::from_raw(42) };
+  let string = ron::to_string(&save).unwrap();
+  assert_eq!(string, "(player:(family:\"Player\",id:42))");
+  assert_eq!(ron::from_str::<Save>(&string).unwrap(), save);
+  assert!(ron::from_str::<ItemSave>(&string).is_err());
+  assert!(ron::from_str::<Save>("(player:(id:42))").is_err());
+}
+
+#[test]
+fn next_id_relaxed() {
+  const THREADS: usize = 8;
+  const IDS_PER_THREAD: usize = 10_000;
+
+  let context = Arc::new(AtomicIdContext::<()>::new());
+  let handles = (0..THREADS)
+    .map(|_| {
+      let context = Arc::clone(&context);
+      thread::spawn(move || {
+        (0..IDS_PER_THREAD).map(|_| context.next_id_relaxed()).collect::<Vec<Id>>()
+      })
+    })
+    .collect::<Vec<_>>();
+
+  let mut ids = HashSet::new();
+  for handle in handles {
+    for id in handle.join().unwrap() {
+      assert!(ids.insert(id), "duplicate id {id:?}");
+    };
+  };
+
+  assert_eq!(ids.len(), THREADS * IDS_PER_THREAD);
+  assert_eq!(context.next_id(), Id::from_raw((THREADS * IDS_PER_THREAD) as RawId));
+}
+
+#[test]
+fn id_slot_map() {
+  let mut map = IdSlotMap::<(), &str>::new();
+  let a = map.insert("a");
+  let b = map.insert("b");
+  assert_eq!(map.len(), 2);
+  assert_eq!(map.get(a), Some(&"a"));
+
+  assert_eq!(map.remove(a), Some("a"));
+  assert_eq!(map.remove(a), None);
+  let c = map.insert("c");
+  assert_eq!(c.index(), a.index());
+  assert_ne!(c, a);
+  assert_eq!(map.get(a), None);
+  assert!(!map.contains(a));
+  assert_eq!(map.get(c), Some(&"c"));
+  *map.get_mut(b).unwrap() = "B";
+  assert_eq!(map.iter().collect::<Vec<_>>(), [(c, &"c"), (b, &"B")]);
+
+  map.clear();
+  assert!(map.is_empty());
+  assert_eq!(map.get(b), None);
+  assert_eq!(map.get(GenId::from_raw(7, 0)), None);
+}
+
+#[test]
+fn raw_slices() {
+  let ids = [Id::<()>::from_raw(3), Id::from_raw(1), Id::from_raw(4)];
+  let raw = Id::as_raw_slice(&ids);
+  assert_eq!(raw, [3, 1, 4]);
+  assert_eq!(Id::<()>::from_raw_slice(raw), ids);
+  assert!(Id::<()>::from_raw_slice(&[]).is_empty());
+}
+
+#[test]
+fn display_tagged() {
+  use ids::IdFamily;
+
+  enum Player {}
+
+  impl IdFamily for Player {
+    const NAME: &'static str = "Player";
+  }
+
+  let player = Id::<Player>::from_raw(42);
+  assert_eq!(player.display_tagged().to_string(), "Player#42");
+  assert_eq!(format!("{:?}", player.display_tagged()), "Player#42");
+  assert_eq!(player.to_string(), "42");
+  assert_eq!(Id::<()>::from_raw(7).to_string(), "7");
+  assert_eq!("42".parse::<Id<Player>>(), Ok(player));
+  assert!("Player#42".parse::<Id<Player>>().is_err());
+}
+
+#[cfg(feature = "map")]
+#[test]
+fn id_map_bump_context_to() {
+  let mut map = IdMap::<&str>::new();
+  map.insert(Id::from_raw(100), "imported");
+  map.bump_context_to(Id::from_raw(100));
+  assert_eq!(map.insert_new("new"), Id::from_raw(101));
+
+  map.bump_context_to(Id::from_raw(5));
+  assert_eq!(map.insert_new("newer"), Id::from_raw(102));
+}
+
+#[cfg(feature = "map")]
+#[test]
+fn id_map_bump_context_to_max() {
+  let max = Id::from_raw(RawId::MAX);
+  let mut map = [(max, "last")].into_iter().collect::<IdMap<&str>>();
+  map.bump_context_to(max);
+  assert_eq!(map.get(max), Some(&"last"));
+}
+
+#[cfg(feature = "map")]
+#[test]
+#[should_panic(expected = "id context exhausted")]
+fn id_map_bump_context_to_max_insert_new() {
+  let mut map = [(Id::from_raw(RawId::MAX), "last")].into_iter().collect::<IdMap<&str>>();
+  // the maximum ID is taken, so there is no ID left to hand out
+  map.insert_new("overflow");
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_id() {
+  use arbitrary::{Arbitrary, Unstructured};
+
+  let bytes = (42 as RawId).to_le_bytes().repeat(3);
+  let mut u = Unstructured::new(&bytes);
+  assert_eq!(Id::<()>::arbitrary(&mut u).unwrap(), Id::from_raw(42));
+  assert_eq!(IdContext::<()>::arbitrary(&mut u).unwrap().current_id(), 42);
+  assert_eq!(AtomicIdContext::<()>::arbitrary(&mut u).unwrap().next_id(), Id::from_raw(42));
+}
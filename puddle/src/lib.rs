@@ -1,15 +1,21 @@
 use std::marker::PhantomData;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 
-use crossbeam::channel::{Sender, Receiver, IntoIter as RecvIter, bounded};
+use crossbeam::channel::{Sender, Receiver, IntoIter as RecvIter, TryIter as RecvTryIter, bounded};
 use threadpool::ThreadPool;
 
 
 
+/// A thread pool that runs jobs through an [`Executor`] and collects their outputs.
+///
+/// Dropping a puddle without calling [`Puddle::finish`] detaches any outstanding jobs:
+/// they continue to run on the pool's threads, but their outputs are discarded.
 pub struct Puddle<E: Executor> {
   threadpool: ThreadPool,
   sender: Sender<E::Output>,
   receiver: Receiver<E::Output>,
+  progress: Arc<Progress>,
   executor: E
 }
 
@@ -21,6 +27,7 @@ impl<E: Executor> Puddle<E> {
       threadpool,
       sender,
       receiver,
+      progress: Arc::default(),
       executor
     }
   }
@@ -32,6 +39,7 @@ impl<E: Executor> Puddle<E> {
       threadpool,
       sender,
       receiver,
+      progress: Arc::default(),
       executor
     }
   }
@@ -51,15 +59,53 @@ impl<E: Executor> Puddle<E> {
     self.threadpool.max_count()
   }
 
+  /// Changes the number of worker threads in the pool.
+  /// When shrinking, workers that are currently running a job are allowed to finish it before exiting.
+  #[inline]
+  pub fn set_num_threads(&mut self, num_threads: usize) {
+    self.threadpool.set_num_threads(num_threads);
+  }
+
+  /// Returns the number of jobs that have completed so far.
+  #[inline]
+  pub fn completed_count(&self) -> usize {
+    self.progress.completed.load(Ordering::SeqCst)
+  }
+
+  /// Registers a callback that is invoked on the worker thread each time a job completes,
+  /// with the number of jobs that have completed so far. Replaces any previous callback.
+  pub fn on_complete(&self, f: impl Fn(usize) + Send + Sync + 'static) {
+    *self.progress.callback.write().unwrap() = Some(Arc::new(f));
+  }
+
   pub fn execute(&self, input: E::Input) where E: Clone {
     let sender = self.sender.clone();
     let executor = self.executor.clone();
+    let progress = Arc::clone(&self.progress);
     self.threadpool.execute(move || {
       let output = executor.execute(input);
-      sender.send(output).unwrap();
+      // the puddle may have been dropped, in which case nobody wants the output
+      let _ = sender.send(output);
+      progress.complete();
     });
   }
 
+  /// Submits a job whose output is delivered to the returned handle
+  /// instead of being collected by this puddle.
+  pub fn execute_handle(&self, input: E::Input) -> JobHandle<E::Output> where E: Clone {
+    let (sender, receiver) = bounded(1);
+    let executor = self.executor.clone();
+    let progress = Arc::clone(&self.progress);
+    self.threadpool.execute(move || {
+      let output = executor.execute(input);
+      // the handle may have been dropped, in which case nobody wants the output
+      let _ = sender.send(output);
+      progress.complete();
+    });
+
+    JobHandle { receiver }
+  }
+
   pub fn execute_iter<I>(&self, iter: I)
   where E: Clone, I: IntoIterator<Item = E::Input> {
     for input in iter {
@@ -67,13 +113,71 @@ impl<E: Executor> Puddle<E> {
     };
   }
 
+  /// Returns an iterator over the outputs that are currently available, without blocking.
+  #[inline]
+  pub fn try_iter(&self) -> PuddleTryIter<'_, E> {
+    self.receiver.try_iter()
+  }
+
   #[inline]
   pub fn finish(self) -> PuddleIter<E> {
     self.receiver.into_iter()
   }
 }
 
+impl<E, O, Err> Puddle<E>
+where E: Executor<Output = Result<O, Err>> {
+  /// Waits for all jobs to finish, partitioning their outputs into successes and failures.
+  pub fn finish_results(self) -> (Vec<O>, Vec<Err>) {
+    let mut successes = Vec::new();
+    let mut failures = Vec::new();
+    for output in self.finish() {
+      match output {
+        Ok(value) => successes.push(value),
+        Err(error) => failures.push(error)
+      };
+    };
+
+    (successes, failures)
+  }
+}
+
+/// A handle to the output of a single job, see [`Puddle::execute_handle`].
+#[derive(Debug)]
+pub struct JobHandle<O> {
+  receiver: Receiver<O>
+}
+
+impl<O> JobHandle<O> {
+  /// Blocks until the job has finished, returning its output.
+  /// Panics if the job panicked.
+  pub fn join(self) -> O {
+    self.receiver.recv().expect("job panicked before producing an output")
+  }
+}
+
+type ProgressCallback = Arc<dyn Fn(usize) + Send + Sync>;
+
+#[derive(Default)]
+struct Progress {
+  completed: AtomicUsize,
+  callback: RwLock<Option<ProgressCallback>>
+}
+
+impl Progress {
+  fn complete(&self) {
+    let completed = self.completed.fetch_add(1, Ordering::SeqCst) + 1;
+    // the lock is released before calling back, so that the callback may itself
+    // call `Puddle::on_complete` without deadlocking
+    let callback = self.callback.read().unwrap().clone();
+    if let Some(callback) = callback {
+      callback(completed);
+    };
+  }
+}
+
 pub type PuddleIter<E> = RecvIter<<E as Executor>::Output>;
+pub type PuddleTryIter<'a, E> = RecvTryIter<'a, <E as Executor>::Output>;
 
 impl<E: Executor> IntoIterator for Puddle<E> {
   type Item = E::Output;
@@ -87,11 +191,45 @@ impl<E: Executor> IntoIterator for Puddle<E> {
 
 
 
+/// Applies `f` to every input in parallel on a temporary pool of `num_threads` threads,
+/// returning the outputs in the same order as their inputs.
+pub fn collect_map<I, T, O>(num_threads: usize, inputs: I, f: fn(T) -> O) -> Vec<O>
+where I: IntoIterator<Item = T>, T: Send + 'static, O: Send + 'static {
+  let puddle = Puddle::new(num_threads, Enumerated(f));
+  puddle.execute_iter(inputs.into_iter().enumerate());
+  let mut outputs = puddle.finish().collect::<Vec<(usize, O)>>();
+  outputs.sort_unstable_by_key(|&(index, _)| index);
+  outputs.into_iter().map(|(_, output)| output).collect()
+}
+
+/// Wraps an executor to carry the index of each input through to its output.
+#[derive(Debug, Clone, Copy)]
+struct Enumerated<E>(E);
+
+impl<E: Executor> Executor for Enumerated<E> {
+  type Input = (usize, E::Input);
+  type Output = (usize, E::Output);
+
+  #[inline]
+  fn execute(&self, (index, input): Self::Input) -> Self::Output {
+    (index, self.0.execute(input))
+  }
+}
+
+
+
 pub trait Executor: Send + Sync + 'static {
   type Input: Send + 'static;
   type Output: Send + 'static;
 
   fn execute(&self, input: Self::Input) -> Self::Output;
+
+  /// Wraps this executor so that `f` is applied to each output on the worker thread.
+  #[inline]
+  fn map<F, O>(self, f: F) -> MapExecutor<Self, F>
+  where Self: Sized, F: Fn(Self::Output) -> O + Send + Sync + 'static, O: Send + 'static {
+    MapExecutor { executor: self, f }
+  }
 }
 
 impl<E, I, O> Executor for Arc<E>
@@ -107,6 +245,26 @@ where E: Executor<Input = I, Output = O>, I: Send + 'static, O: Send + 'static {
 
 
 
+/// An executor that transforms the outputs of another executor, see [`Executor::map`].
+#[derive(Debug, Clone, Copy)]
+pub struct MapExecutor<E, F> {
+  executor: E,
+  f: F
+}
+
+impl<E, F, O> Executor for MapExecutor<E, F>
+where E: Executor, F: Fn(E::Output) -> O + Send + Sync + 'static, O: Send + 'static {
+  type Input = E::Input;
+  type Output = O;
+
+  #[inline]
+  fn execute(&self, input: E::Input) -> O {
+    (self.f)(self.executor.execute(input))
+  }
+}
+
+
+
 impl<I, O> Executor for fn(I) -> O
 where I: Send + 'static, O: Send + 'static {
   type Input = I;
@@ -124,7 +282,7 @@ pub struct Thunk<F>(PhantomData<F>);
 impl<F> Clone for Thunk<F> {
   #[inline]
   fn clone(&self) -> Self {
-    Self(PhantomData)
+    *self
   }
 }
 
@@ -0,0 +1,176 @@
+extern crate puddle;
+
+use puddle::{Executor, Puddle};
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn double(x: u32) -> u32 {
+  x * 2
+}
+
+#[test]
+fn set_num_threads() {
+  let mut puddle = Puddle::new(2, double as fn(u32) -> u32);
+  assert_eq!(puddle.max_count(), 2);
+  puddle.set_num_threads(4);
+  assert_eq!(puddle.max_count(), 4);
+
+  puddle.execute_iter(0..8);
+  let mut outputs = puddle.finish().collect::<Vec<u32>>();
+  outputs.sort_unstable();
+  assert_eq!(outputs, [0, 2, 4, 6, 8, 10, 12, 14]);
+}
+
+#[test]
+fn try_iter() {
+  let puddle = Puddle::new(2, double as fn(u32) -> u32);
+  assert_eq!(puddle.try_iter().count(), 0);
+
+  puddle.execute(21);
+  let deadline = Instant::now() + Duration::from_secs(5);
+  let mut outputs = Vec::new();
+  while outputs.is_empty() && Instant::now() < deadline {
+    outputs.extend(puddle.try_iter());
+  };
+
+  assert_eq!(outputs, [42]);
+  assert_eq!(puddle.try_iter().count(), 0);
+}
+
+#[test]
+fn map_executor() {
+  let executor = (double as fn(u32) -> u32).map(|x| x + 1);
+  assert_eq!(executor.execute(4), 9);
+
+  let puddle = Puddle::new(2, executor);
+  puddle.execute_iter(0..4);
+  let mut outputs = puddle.finish().collect::<Vec<u32>>();
+  outputs.sort_unstable();
+  assert_eq!(outputs, [1, 3, 5, 7]);
+}
+
+#[test]
+fn finish_results() {
+  fn checked_digit(x: u32) -> Result<u32, u32> {
+    if x < 10 { Ok(x) } else { Err(x) }
+  }
+
+  let puddle = Puddle::new(2, checked_digit as fn(u32) -> Result<u32, u32>);
+  puddle.execute_iter([3, 12, 0, 7, 10, 1, 25]);
+  let (mut successes, mut failures) = puddle.finish_results();
+  successes.sort_unstable();
+  failures.sort_unstable();
+  assert_eq!(successes, [0, 1, 3, 7]);
+  assert_eq!(failures, [10, 12, 25]);
+}
+
+#[test]
+fn drop_with_queued_jobs() {
+  #[derive(Default)]
+  struct Outcome {
+    dropped: AtomicUsize,
+    panicked: AtomicBool
+  }
+
+  // a worker that panics while sending its output drops the token during unwinding
+  struct Token(Arc<Outcome>);
+
+  impl Drop for Token {
+    fn drop(&mut self) {
+      if thread::panicking() {
+        self.0.panicked.store(true, Ordering::SeqCst);
+      };
+
+      self.0.dropped.fetch_add(1, Ordering::SeqCst);
+    }
+  }
+
+  #[derive(Clone)]
+  struct Slow(Arc<Outcome>);
+
+  impl Executor for Slow {
+    type Input = ();
+    type Output = Token;
+
+    fn execute(&self, (): ()) -> Token {
+      thread::sleep(Duration::from_millis(20));
+      Token(Arc::clone(&self.0))
+    }
+  }
+
+  let outcome = Arc::new(Outcome::default());
+  let puddle = Puddle::new(2, Slow(Arc::clone(&outcome)));
+  puddle.execute_iter([(); 6]);
+  drop(puddle);
+
+  let deadline = Instant::now() + Duration::from_secs(5);
+  while outcome.dropped.load(Ordering::SeqCst) < 6 && Instant::now() < deadline {
+    thread::sleep(Duration::from_millis(5));
+  };
+
+  assert_eq!(outcome.dropped.load(Ordering::SeqCst), 6);
+  assert!(!outcome.panicked.load(Ordering::SeqCst));
+}
+
+#[test]
+fn on_complete() {
+  let counts = Arc::new(Mutex::new(Vec::new()));
+  let puddle = Puddle::new(3, double as fn(u32) -> u32);
+  let callback_counts = Arc::clone(&counts);
+  puddle.on_complete(move |completed| callback_counts.lock().unwrap().push(completed));
+
+  puddle.execute_iter(0..10);
+  assert_eq!(puddle.finish().count(), 10);
+
+  let mut counts = counts.lock().unwrap().clone();
+  counts.sort_unstable();
+  assert_eq!(counts, (1..=10).collect::<Vec<usize>>());
+}
+
+#[test]
+fn on_complete_reentrant() {
+  let puddle = Arc::new(Puddle::new(1, double as fn(u32) -> u32));
+  let counts = Arc::new(Mutex::new(Vec::new()));
+  let callback_counts = Arc::clone(&counts);
+  let callback_puddle = Arc::downgrade(&puddle);
+  puddle.on_complete(move |completed| {
+    callback_counts.lock().unwrap().push(completed);
+    // replacing the callback from within itself must not deadlock
+    if let Some(puddle) = callback_puddle.upgrade() {
+      puddle.on_complete(|_| ());
+    };
+  });
+
+  puddle.execute(1);
+  let start = Instant::now();
+  while puddle.completed_count() < 1 || puddle.active_count() > 0 {
+    assert!(start.elapsed() < Duration::from_secs(5), "callback deadlocked");
+    thread::yield_now();
+  };
+
+  let puddle = Arc::try_unwrap(puddle).ok().unwrap();
+  assert_eq!(puddle.finish().count(), 1);
+  assert_eq!(*counts.lock().unwrap(), [1]);
+}
+
+#[test]
+fn execute_handle() {
+  let puddle = Puddle::new(2, double as fn(u32) -> u32);
+  let first = puddle.execute_handle(3);
+  let second = puddle.execute_handle(5);
+  assert_eq!(second.join(), 10);
+  assert_eq!(first.join(), 6);
+
+  // outputs of handled jobs are not collected by the puddle
+  assert_eq!(puddle.finish().count(), 0);
+}
+
+#[test]
+fn collect_map() {
+  let sequential = (0..100).map(double).collect::<Vec<u32>>();
+  assert_eq!(puddle::collect_map(4, 0..100, double), sequential);
+  assert!(puddle::collect_map(2, Vec::<u32>::new(), double).is_empty());
+}
@@ -4,6 +4,9 @@ pub trait SliceExt<T> {
   fn to_mut_array<const N: usize>(&mut self) -> Option<&mut [T; N]>;
   fn windows_mut_each<F>(&mut self, len: usize, f: F) where F: FnMut(&mut [T]);
   fn array_windows_mut_each<F, const N: usize>(&mut self, f: F) where F: FnMut(&mut [T; N]);
+  /// Similar to [`slice::chunk_by`], but groups consecutive elements by a key
+  /// rather than by comparing adjacent pairs.
+  fn group_runs<K, F>(&self, key: F) -> GroupRuns<'_, T, F> where K: PartialEq, F: FnMut(&T) -> K;
 }
 
 impl<T> SliceExt<T> for [T] {
@@ -51,4 +54,55 @@ impl<T> SliceExt<T> for [T] {
       f(slice.to_mut_array().unwrap());
     };
   }
+
+  #[inline]
+  fn group_runs<K, F>(&self, key: F) -> GroupRuns<'_, T, F>
+  where K: PartialEq, F: FnMut(&T) -> K {
+    GroupRuns { slice: self, key }
+  }
+}
+
+/// An iterator over maximal runs of consecutive elements sharing a key, see [`SliceExt::group_runs`].
+#[derive(Debug, Clone)]
+pub struct GroupRuns<'a, T, F> {
+  slice: &'a [T],
+  key: F
+}
+
+impl<'a, T, K, F> Iterator for GroupRuns<'a, T, F>
+where K: PartialEq, F: FnMut(&T) -> K {
+  type Item = &'a [T];
+
+  fn next(&mut self) -> Option<&'a [T]> {
+    let (first, rest) = self.slice.split_first()?;
+    let first_key = (self.key)(first);
+    let len = 1 + rest.iter().take_while(|value| (self.key)(value) == first_key).count();
+    let (run, remainder) = self.slice.split_at(len);
+    self.slice = remainder;
+    Some(run)
+  }
+}
+
+impl<'a, T, K, F> std::iter::FusedIterator for GroupRuns<'a, T, F>
+where K: PartialEq, F: FnMut(&T) -> K {}
+
+/// Removes consecutive elements of a vector that map to the same key,
+/// keeping the first element of each run and returning the removed elements in order.
+pub fn dedup_consecutive<T, K, F>(v: &mut Vec<T>, mut key: F) -> Vec<T>
+where K: PartialEq, F: FnMut(&T) -> K {
+  let mut kept = Vec::with_capacity(v.len());
+  let mut removed = Vec::new();
+  let mut last_key = None;
+  for value in v.drain(..) {
+    let value_key = key(&value);
+    if last_key.as_ref() == Some(&value_key) {
+      removed.push(value);
+    } else {
+      last_key = Some(value_key);
+      kept.push(value);
+    };
+  };
+
+  *v = kept;
+  removed
 }
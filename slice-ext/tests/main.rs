@@ -0,0 +1,24 @@
+extern crate slice_ext;
+
+use slice_ext::{dedup_consecutive, SliceExt};
+
+#[test]
+fn dedup_consecutive_by_key() {
+  let mut v = vec![(1, 'a'), (1, 'b'), (2, 'c'), (2, 'd'), (2, 'e'), (1, 'f'), (3, 'g')];
+  let removed = dedup_consecutive(&mut v, |&(key, _)| key);
+  assert_eq!(v, [(1, 'a'), (2, 'c'), (1, 'f'), (3, 'g')]);
+  assert_eq!(removed, [(1, 'b'), (2, 'd'), (2, 'e')]);
+
+  let mut v: Vec<u32> = Vec::new();
+  assert!(dedup_consecutive(&mut v, |&x| x).is_empty());
+}
+
+#[test]
+fn group_runs() {
+  let values = [1, 3, 5, 2, 4, 7, 8, 8];
+  let runs = values.group_runs(|&x| x % 2).collect::<Vec<&[i32]>>();
+  assert_eq!(runs, [&[1, 3, 5][..], &[2, 4], &[7], &[8, 8]]);
+
+  let empty: [i32; 0] = [];
+  assert_eq!(empty.group_runs(|&x| x).count(), 0);
+}
@@ -21,6 +21,13 @@ impl<T> UOrd<T> {
     UOrd { min, max }
   }
 
+  /// Creates a new pair from two elements that are already in order, usable in const contexts.
+  /// If `min` is greater than `max`, the behavior of the resulting pair is unspecified (but safe).
+  #[inline(always)]
+  pub const fn new_unchecked(min: T, max: T) -> Self {
+    UOrd { min, max }
+  }
+
   /// Returns the lesser of the two elements, based on `T`'s `Ord` implementation.
   #[inline(always)]
   pub const fn min(&self) -> &T {
@@ -35,15 +42,15 @@ impl<T> UOrd<T> {
 
   /// Returns `true` if one of the two contained elements equals `x`.
   #[inline]
-  pub fn contains<Q: ?Sized>(&self, x: &Q) -> bool
-  where T: Borrow<Q>, Q: Eq {
+  pub fn contains<Q>(&self, x: &Q) -> bool
+  where T: Borrow<Q>, Q: ?Sized + Eq {
     self.min.borrow() == x || self.max.borrow() == x
   }
 
   /// Returns the opposite elements when one of the contained elements matches the given value.
   #[inline]
-  pub fn other<Q: ?Sized>(&self, x: &Q) -> Option<&T>
-  where T: Borrow<Q>, Q: Eq {
+  pub fn other<Q>(&self, x: &Q) -> Option<&T>
+  where T: Borrow<Q>, Q: ?Sized + Eq {
     if self.max.borrow() == x {
       Some(&self.min)
     } else if self.min.borrow() == x {
@@ -61,8 +68,8 @@ impl<T> UOrd<T> {
   }
 
   /// Replaces one or both elements of this pair with a new value.
-  pub fn replace<Q: ?Sized>(&self, from: &Q, to: T) -> Self
-  where T: Ord + Borrow<Q> + Clone, Q: Eq {
+  pub fn replace<Q>(&self, from: &Q, to: T) -> Self
+  where T: Ord + Borrow<Q> + Clone, Q: ?Sized + Eq {
     self.as_ref().map(|v| if v.borrow() == from { to.clone() } else { v.clone() })
   }
 
@@ -140,10 +147,11 @@ impl<T> UOrd<T> {
   }
 
   #[inline]
-  pub fn iter(&self) -> UOrdIter<T> {
+  pub fn iter(&self) -> UOrdIter<'_, T> {
     self.into_iter()
   }
 
+  /// Mutates both elements of this pair at once, re-sorting them afterwards if needed.
   pub fn inspect<F>(&mut self, mut f: F)
   where T: Ord, F: FnMut(&mut T, &mut T) {
     f(&mut self.min, &mut self.max);
@@ -200,13 +208,15 @@ impl<T: Ord> From<[T; 2]> for UOrd<T> {
   }
 }
 
-impl<T: Ord> From<UOrd<T>> for (T, T) {
+impl<T> From<UOrd<T>> for (T, T) {
+  #[inline(always)]
   fn from(value: UOrd<T>) -> Self {
     value.into_tuple()
   }
 }
 
-impl<T: Ord> From<UOrd<T>> for [T; 2] {
+impl<T> From<UOrd<T>> for [T; 2] {
+  #[inline(always)]
   fn from(value: UOrd<T>) -> Self {
     value.into_array()
   }
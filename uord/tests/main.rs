@@ -0,0 +1,39 @@
+extern crate uord;
+
+use uord::UOrd;
+
+#[test]
+fn const_accessors() {
+  const PAIR: UOrd<u32> = UOrd::new_unchecked(1, 2);
+  const MIN: u32 = *PAIR.min();
+  const MAX: u32 = *PAIR.max();
+  const TUPLE: (&u32, &u32) = PAIR.as_tuple();
+  const ARRAY: [&u32; 2] = PAIR.as_array();
+
+  assert_eq!((MIN, MAX), (1, 2));
+  assert_eq!(TUPLE, (&1, &2));
+  assert_eq!(ARRAY, [&1, &2]);
+  assert_eq!(PAIR, UOrd::new(2, 1));
+}
+
+#[test]
+fn inspect() {
+  let mut pair = UOrd::new(1, 5);
+  pair.inspect(|min, _| *min = 10);
+  assert_eq!(pair.as_tuple(), (&5, &10));
+
+  pair.inspect(|min, max| { *min += 1; *max += 1 });
+  assert_eq!(pair.into_tuple(), (6, 11));
+}
+
+#[test]
+fn conversions() {
+  let tuple: (char, char) = UOrd::new('b', 'a').into();
+  assert_eq!(tuple, ('a', 'b'));
+  let array: [char; 2] = UOrd::from(['z', 'y']).into();
+  assert_eq!(array, ['y', 'z']);
+
+  // elements without an `Ord` impl can still be taken back out
+  let floats: (f64, f64) = UOrd::new_unchecked(0.5, 1.5).into();
+  assert_eq!(floats, (0.5, 1.5));
+}
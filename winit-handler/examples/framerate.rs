@@ -0,0 +1,35 @@
+extern crate winit;
+extern crate winit_handler;
+
+use winit::dpi::PhysicalSize;
+use winit::event_loop::EventLoop;
+use winit::window::WindowBuilder;
+use winit_handler::{EventHandler, WindowState};
+
+use std::time::{Duration, Instant};
+
+struct Main {
+  frames: u32,
+  last_report: Instant
+}
+
+impl EventHandler<()> for Main {
+  fn render(&mut self, _: &WindowState) {
+    self.frames += 1;
+    if self.last_report.elapsed() >= Duration::from_secs(1) {
+      println!("Frames in the last second: {}", self.frames);
+      self.frames = 0;
+      self.last_report = Instant::now();
+    };
+  }
+}
+
+fn main() {
+  let event_loop = EventLoop::new();
+  let window = WindowBuilder::new()
+    .with_title("winit-handler framerate example")
+    .with_inner_size(PhysicalSize::<u32>::from((384, 256)))
+    .build(&event_loop).unwrap();
+  let handler = Main { frames: 0, last_report: Instant::now() };
+  winit_handler::run_with_framerate(event_loop, window, handler, 30);
+}
@@ -0,0 +1,53 @@
+extern crate winit;
+extern crate winit_handler;
+
+use winit::dpi::PhysicalSize;
+use winit::event_loop::{ControlFlow, EventLoop};
+use winit::window::WindowBuilder;
+use winit_handler::{EventHandler, WindowState};
+
+use std::time::{Duration, Instant};
+
+const IDLE_AFTER: Duration = Duration::from_secs(2);
+const IDLE_WAKEUP: Duration = Duration::from_secs(1);
+
+struct Main {
+  last_input: Instant,
+  last_report: Instant,
+  updates: u32
+}
+
+impl EventHandler<()> for Main {
+  fn update(&mut self, window_state: &WindowState) {
+    let input = window_state.input();
+    if input.was_moving() || !input.key_actions().is_empty() || !input.mouse_actions().is_empty() {
+      self.last_input = Instant::now();
+    };
+
+    self.updates += 1;
+    if self.last_report.elapsed() >= Duration::from_secs(1) {
+      println!("Updates in the last second: {}", self.updates);
+      self.updates = 0;
+      self.last_report = Instant::now();
+    };
+  }
+
+  fn control_flow(&mut self, _: &WindowState) -> Option<ControlFlow> {
+    if self.last_input.elapsed() >= IDLE_AFTER {
+      // nothing has happened for a while, only wake up occasionally
+      Some(ControlFlow::WaitUntil(Instant::now() + IDLE_WAKEUP))
+    } else {
+      Some(ControlFlow::Poll)
+    }
+  }
+}
+
+fn main() {
+  let event_loop = EventLoop::new();
+  let window = WindowBuilder::new()
+    .with_title("winit-handler idle example")
+    .with_inner_size(PhysicalSize::<u32>::from((384, 256)))
+    .build(&event_loop).unwrap();
+  let handler = Main { last_input: Instant::now(), last_report: Instant::now(), updates: 0 };
+  winit_handler::run(event_loop, window, handler);
+}
@@ -15,10 +15,17 @@ pub trait EventHandlerFallible<E: Error>: Sized + 'static {
   fn render(&mut self, window_state: &WindowState) -> Result<(), E> { Ok(()) }
   /// Called upon [`Event::MainEventsCleared`][winit::event::Event::MainEventsCleared].
   fn update(&mut self, window_state: &WindowState) -> Result<(), E> { Ok(()) }
+  /// Called just before `update`, with mutable access to the current frame's input,
+  /// see [`EventHandler::filter_input`].
+  fn filter_input(&mut self, input_state: &mut InputState) -> Result<(), E> { Ok(()) }
   /// Called when an event from the keyboard has been received.
   fn keyboard_input(&mut self, window_state: &WindowState, state: KeyState, keycode: Option<VirtualKeyCode>, scancode: ScanCode) -> Result<(), E> { Ok(()) }
   /// Called when the window receives a unicode character.
   fn text_input(&mut self, window_state: &WindowState, ch: char) -> Result<(), E> { Ok(()) }
+  /// Called upon [`WindowEvent::Ime`][winit::event::WindowEvent::Ime], when an input method editor
+  /// has been enabled or disabled, or its composition text has changed or been committed.
+  /// These events are only sent once IME has been allowed with [`Window::set_ime_allowed`][winit::window::Window::set_ime_allowed].
+  fn ime(&mut self, window_state: &WindowState, event: Ime) -> Result<(), E> { Ok(()) }
   /// Called when the cursor has moved on the window.
   fn cursor_moved(&mut self, window_state: &WindowState, pos: (f32, f32)) -> Result<(), E> { Ok(()) }
   /// Called when a mouse button press has been received.
@@ -39,6 +46,10 @@ pub trait EventHandlerFallible<E: Error>: Sized + 'static {
   /// This function does not return a result because a program should (logically) be able to determine whether it should close without fail.
   /// Defaults to an 'always `false`' implementation.
   fn should_exit(&self, window_state: &WindowState) -> bool { false }
+  /// Called after every event has been dispatched, allowing the handler to set the event loop's
+  /// [`ControlFlow`][winit::event_loop::ControlFlow], see [`EventHandler::control_flow`].
+  /// Defaults to an 'always `None`' implementation.
+  fn control_flow(&mut self, window_state: &WindowState) -> Option<ControlFlow> { None }
   /// Called once the event loop has been destroyed and will no longer dispatch any more events.
   /// This is different from the `close` function in that the handler has no choice over the application state.
   /// It is mandatory to implement this function, as it serves as the error handling mechanism.
@@ -77,8 +88,10 @@ where H: EventHandlerFallible<E>, E: Error + 'static {
   handler_functions!{
     fn render(&mut self, window_state: &WindowState);
     fn update(&mut self, window_state: &WindowState);
+    fn filter_input(&mut self, input_state: &mut InputState);
     fn keyboard_input(&mut self, window_state: &WindowState, state: KeyState, keycode: Option<VirtualKeyCode>, scancode: ScanCode);
     fn text_input(&mut self, window_state: &WindowState, ch: char);
+    fn ime(&mut self, window_state: &WindowState, event: Ime);
     fn cursor_moved(&mut self, window_state: &WindowState, pos: (f32, f32));
     fn mouse_input(&mut self, window_state: &WindowState, state: ElementState, button: MouseButton);
     fn mouse_scroll(&mut self, window_state: &WindowState, delta: (f32, f32));
@@ -102,6 +115,11 @@ where H: EventHandlerFallible<E>, E: Error + 'static {
     !self.errors.is_empty() || self.handler.should_exit(window_state)
   }
 
+  #[inline]
+  fn control_flow(&mut self, window_state: &WindowState) -> Option<ControlFlow> {
+    self.handler.control_flow(window_state)
+  }
+
   #[inline]
   fn destroy(self) {
     self.handler.destroy(self.errors)
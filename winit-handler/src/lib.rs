@@ -11,11 +11,12 @@ use serde::{Serialize, Deserialize};
 use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::event::*;
 use winit::event_loop::{EventLoop, ControlFlow};
-use winit::window::Window;
+use winit::window::{Window, WindowId};
 
 use std::ops::Index;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 
 
@@ -26,6 +27,10 @@ pub trait EventHandler<T = ()>: Sized + 'static {
   fn render(&mut self, window_state: &WindowState) {}
   /// Called upon [`Event::MainEventsCleared`][winit::event::Event::MainEventsCleared].
   fn update(&mut self, window_state: &WindowState) {}
+  /// Called upon [`Event::MainEventsCleared`][winit::event::Event::MainEventsCleared], just before `update`,
+  /// with mutable access to the current frame's input. Input consumed here with [`InputState::consume_key`]
+  /// or [`InputState::consume_mouse`] will not be seen by `update` or `render` during the same frame.
+  fn filter_input(&mut self, input_state: &mut InputState) {}
   /// Called when an event is sent from [`EventLoopProxy::send_event`][winit::event_loop::EventLoopProxy::send_event].
   fn user_event(&mut self, window_state: &WindowState, event: T) {}
   /// Called upon [`Event::DeviceEvent`][winit::event::Event::DeviceEvent].
@@ -34,6 +39,10 @@ pub trait EventHandler<T = ()>: Sized + 'static {
   fn keyboard_input(&mut self, window_state: &WindowState, state: KeyState, keycode: Option<VirtualKeyCode>, scancode: ScanCode) {}
   /// Called when the window receives a unicode character.
   fn text_input(&mut self, window_state: &WindowState, ch: char) {}
+  /// Called upon [`WindowEvent::Ime`][winit::event::WindowEvent::Ime], when an input method editor
+  /// has been enabled or disabled, or its composition text has changed or been committed.
+  /// These events are only sent once IME has been allowed with [`Window::set_ime_allowed`][winit::window::Window::set_ime_allowed].
+  fn ime(&mut self, window_state: &WindowState, event: Ime) {}
   /// Called when the cursor has moved on the window.
   fn cursor_moved(&mut self, window_state: &WindowState, pos: (f32, f32)) {}
   /// Called when a mouse button press has been received.
@@ -53,6 +62,12 @@ pub trait EventHandler<T = ()>: Sized + 'static {
   /// Instructs the event dispatcher whether the handler wants the application to exit.
   /// Defaults to an 'always `false`' implementation.
   fn should_exit(&self, window_state: &WindowState) -> bool { false }
+  /// Called after every event has been dispatched, allowing the handler to set the event loop's
+  /// [`ControlFlow`][winit::event_loop::ControlFlow], for example to sleep with `WaitUntil` while idle.
+  /// Returning `Some` replaces the current control flow, which persists until it is changed again,
+  /// while `None` leaves it as is. This cannot cancel an exit that is already in progress.
+  /// Defaults to an 'always `None`' implementation.
+  fn control_flow(&mut self, window_state: &WindowState) -> Option<ControlFlow> { None }
   /// Called once the event loop has been destroyed and will no longer dispatch any more events.
   /// This is different from the `close` function in that the handler has no choice over the application state.
   fn destroy(self) {}
@@ -100,10 +115,31 @@ pub struct InputState {
   keys_held_scancode: BitArray<[u32; SCANCODE_BITS / 32]>,
   modifiers_state: ModifiersState,
   scroll_rel: (f32, f32),
-  text: String
+  text: String,
+  preedit: String,
+  preedit_cursor: Option<(usize, usize)>
 }
 
 impl InputState {
+  /// Marks the given keys as held, for building a predetermined input state
+  /// to pass to [`WindowState::with_input`].
+  pub fn with_keys_held(mut self, keycodes: impl IntoIterator<Item = VirtualKeyCode>) -> Self {
+    for keycode in keycodes {
+      self.keys_held_keycode.set(keycode as usize, true);
+    };
+
+    self
+  }
+
+  /// Marks the given mouse buttons as held, see [`InputState::with_keys_held`].
+  pub fn with_buttons_held(mut self, buttons: impl IntoIterator<Item = MouseButton>) -> Self {
+    for button in buttons {
+      self.set_button_value(button, true);
+    };
+
+    self
+  }
+
   #[inline]
   pub fn cursor_pos(&self) -> Option<(f32, f32)> {
     self.cursor_pos
@@ -168,6 +204,42 @@ impl InputState {
     find_key_action!(self, keycode, KeyState::Released)
   }
 
+  /// Returns an iterator over the keys that were pressed during the current frame.
+  pub fn keys_pressed(&self) -> impl Iterator<Item = VirtualKeyCode> + '_ {
+    self.keys_with_state(KeyState::Pressed)
+  }
+
+  /// Returns an iterator over the keys that were released during the current frame.
+  pub fn keys_released(&self) -> impl Iterator<Item = VirtualKeyCode> + '_ {
+    self.keys_with_state(KeyState::Released)
+  }
+
+  fn keys_with_state(&self, state: KeyState) -> impl Iterator<Item = VirtualKeyCode> + '_ {
+    self.key_actions.iter()
+      .filter(move |action| action.state == state)
+      .map(|action| action.keycode)
+  }
+
+  /// Removes all actions for the given key from the current frame, so that later code
+  /// examining this input state will not react to them. Useful for layering input handling
+  /// from [`EventHandler::filter_input`], the only place where a handler can modify the
+  /// window state's input. Elsewhere, this only affects an input state the caller owns.
+  /// Returns `true` if any actions were removed. Does not affect whether the key is held.
+  pub fn consume_key(&mut self, keycode: VirtualKeyCode) -> bool {
+    let len = self.key_actions.len();
+    self.key_actions.retain(|action| action.keycode != keycode);
+    self.key_actions.len() != len
+  }
+
+  /// Removes all actions for the given mouse button from the current frame,
+  /// see [`consume_key`][InputState::consume_key].
+  /// Returns `true` if any actions were removed. Does not affect whether the button is held.
+  pub fn consume_mouse(&mut self, button: MouseButton) -> bool {
+    let len = self.mouse_actions.len();
+    self.mouse_actions.retain(|action| action.button != button);
+    self.mouse_actions.len() != len
+  }
+
   /// Whether the mouse moved during the current frame.
   pub fn was_moving(&self) -> bool {
     self.cursor_pos != self.cursor_pos_prev
@@ -190,6 +262,20 @@ impl InputState {
     &self.text
   }
 
+  /// Returns the text currently being composed by an input method editor,
+  /// which is empty when no composition is in progress.
+  #[inline]
+  pub fn preedit(&self) -> &str {
+    &self.preedit
+  }
+
+  /// Returns the byte range of the cursor within the [`preedit`][InputState::preedit] text,
+  /// or `None` if the cursor should be hidden.
+  #[inline]
+  pub fn preedit_cursor(&self) -> Option<(usize, usize)> {
+    self.preedit_cursor
+  }
+
   #[inline]
   pub fn modifiers(&self) -> ModifiersState {
     self.modifiers_state
@@ -225,7 +311,8 @@ impl InputState {
 
     if let Some(keycode) = keycode {
       self.keys_held_keycode.set(keycode as usize, cond);
-      self.key_actions.push(KeyAction { keycode, scancode, state });
+      let modifiers = self.modifiers_state;
+      self.key_actions.push(KeyAction { keycode, scancode, state, modifiers });
     };
 
     if scancode < SCANCODE_MAX {
@@ -235,6 +322,19 @@ impl InputState {
     (state, keycode, scancode)
   }
 
+  fn handle_ime(&mut self, event: &Ime) {
+    match event {
+      Ime::Preedit(preedit, cursor) => {
+        self.preedit.clone_from(preedit);
+        self.preedit_cursor = *cursor;
+      },
+      Ime::Enabled | Ime::Commit(_) | Ime::Disabled => {
+        self.preedit.clear();
+        self.preedit_cursor = None;
+      }
+    };
+  }
+
   fn handle_mouse_input(&mut self, state: ElementState, button: MouseButton) {
     let cond = element_state_to_bool(state);
     self.set_button_value(button, cond);
@@ -270,7 +370,9 @@ impl Default for InputState {
       keys_held_scancode: BitArray::default(),
       modifiers_state: ModifiersState::default(),
       scroll_rel: (0.0, 0.0),
-      text: String::new()
+      text: String::new(),
+      preedit: String::new(),
+      preedit_cursor: None
     }
   }
 }
@@ -316,7 +418,9 @@ impl Index<MouseButton> for InputState {
 pub struct KeyAction {
   pub keycode: VirtualKeyCode,
   pub scancode: ScanCode,
-  pub state: KeyState
+  pub state: KeyState,
+  /// The modifiers that were active when this action occurred.
+  pub modifiers: ModifiersState
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -347,13 +451,39 @@ impl From<KeyState> for ElementState {
 
 
 
+/// Paces redraws to a fixed interval, see [`run_with_framerate`].
+#[derive(Debug, Clone, Copy)]
+struct FrameLimiter {
+  interval: Duration,
+  next_frame: Instant
+}
+
+impl FrameLimiter {
+  fn new(interval: Duration) -> Self {
+    FrameLimiter { interval, next_frame: Instant::now() }
+  }
+
+  /// Returns whether a frame is due, scheduling the next one if so.
+  fn tick(&mut self, now: Instant) -> bool {
+    if now < self.next_frame { return false };
+    self.next_frame += self.interval;
+    // don't try to catch up on frames that were missed
+    if self.next_frame <= now {
+      self.next_frame = now + self.interval;
+    };
+
+    true
+  }
+}
+
 #[derive(Debug, Clone)]
 pub struct WindowState {
   input_state: InputState,
   dropped_file: Option<PathBuf>,
   scale_factor: f64,
   window_size: PhysicalSize<u32>,
-  window: Rc<Window>
+  frame_limiter: Option<FrameLimiter>,
+  window: Option<Rc<Window>>
 }
 
 impl WindowState {
@@ -362,8 +492,30 @@ impl WindowState {
       input_state: InputState::default(),
       dropped_file: None,
       scale_factor: window.scale_factor(),
-      window_size: window.inner_size().into(),
-      window
+      window_size: window.inner_size(),
+      frame_limiter: None,
+      window: Some(window)
+    }
+  }
+
+  /// Creates a window state with a predetermined input state, for example to drive
+  /// an [`EventHandler`] deterministically in tests or when replaying input.
+  pub fn with_input(window: impl Into<Rc<Window>>, input_state: InputState) -> Self {
+    WindowState { input_state, ..WindowState::new(window.into()) }
+  }
+
+  /// Creates a window state that is not backed by a real window, with the given size and
+  /// a predetermined input state, see [`WindowState::with_input`].
+  /// Since creating a window requires an event loop, this allows driving an [`EventHandler`] in unit tests.
+  /// Window events are accepted regardless of their window ID, and redraws are never requested.
+  pub fn headless(window_size: (u32, u32), scale_factor: f64, input_state: InputState) -> Self {
+    WindowState {
+      input_state,
+      dropped_file: None,
+      scale_factor,
+      window_size: window_size.into(),
+      frame_limiter: None,
+      window: None
     }
   }
 
@@ -392,14 +544,21 @@ impl WindowState {
     self.window_size.into()
   }
 
+  /// Panics if this window state was created with [`WindowState::headless`].
   #[inline]
   pub fn window(&self) -> &Window {
-    &self.window
+    self.window.as_deref().expect("headless window state has no window")
   }
 
+  /// Panics if this window state was created with [`WindowState::headless`].
   #[inline]
   pub fn window_ref(&self) -> Rc<Window> {
-    Rc::clone(&self.window)
+    Rc::clone(self.window.as_ref().expect("headless window state has no window"))
+  }
+
+  /// Whether events for the given window should be dispatched by this window state.
+  fn is_window(&self, window_id: WindowId) -> bool {
+    self.window.as_ref().is_none_or(|window| window.id() == window_id)
   }
 
   /// Only returns `Some` when the given cursor position is within frame.
@@ -416,11 +575,9 @@ impl WindowState {
   fn handle_event<T, H: EventHandler<T>>(&mut self, handler: &mut H, event: Event<T>, cf: &mut ControlFlow) {
     match event {
       Event::NewEvents(_) => self.reset(),
-      Event::WindowEvent { event, window_id } if self.window().id() == window_id => match event {
-        WindowEvent::CloseRequested => {
-          if handler.close(self) {
-            *cf = ControlFlow::ExitWithCode(0);
-          };
+      Event::WindowEvent { event, window_id } if self.is_window(window_id) => match event {
+        WindowEvent::CloseRequested if handler.close(self) => {
+          *cf = ControlFlow::ExitWithCode(0);
         },
         WindowEvent::Destroyed => (),
         WindowEvent::Focused(false) => {
@@ -451,6 +608,10 @@ impl WindowState {
           self.input_state.text.push(ch);
           handler.text_input(self, ch);
         },
+        WindowEvent::Ime(event) => {
+          self.input_state.handle_ime(&event);
+          handler.ime(self, event);
+        },
         WindowEvent::CursorMoved { position, .. } => {
           if let Some(position) = self.clip_cursor_pos(position) {
             self.input_state.cursor_pos = Some(position);
@@ -474,12 +635,26 @@ impl WindowState {
       Event::DeviceEvent { device_id, event } => {
         handler.device_event(self, device_id, event);
       },
-      Event::RedrawRequested(window_id) if self.window().id() == window_id => {
+      Event::RedrawRequested(window_id) if self.is_window(window_id) => {
         handler.render(self);
       },
       Event::MainEventsCleared => {
+        handler.filter_input(&mut self.input_state);
         handler.update(self);
-        self.window().request_redraw();
+        match self.frame_limiter.as_mut() {
+          None => if let Some(window) = &self.window {
+            window.request_redraw();
+          },
+          Some(frame_limiter) => {
+            if frame_limiter.tick(Instant::now()) {
+              if let Some(window) = &self.window {
+                window.request_redraw();
+              };
+            };
+
+            *cf = ControlFlow::WaitUntil(frame_limiter.next_frame);
+          }
+        };
       },
       Event::UserEvent(t) => handler.user_event(self, t),
       _ => ()
@@ -498,7 +673,24 @@ macro_rules! unwrap_unreachable {
 
 pub fn run<T, W, H>(event_loop: EventLoop<T>, source: W, handler: H) -> !
 where W: Into<Rc<Window>>, H: EventHandler<T> {
+  run_window_state(event_loop, WindowState::new(source.into()), handler)
+}
+
+/// Like [`run`], but only redraws the window up to `fps` times per second,
+/// sleeping between frames with [`ControlFlow::WaitUntil`] instead of spinning.
+/// [`EventHandler::update`] is still called whenever new events arrive.
+///
+/// Panics if `fps` is zero.
+pub fn run_with_framerate<T, W, H>(event_loop: EventLoop<T>, source: W, handler: H, fps: u32) -> !
+where W: Into<Rc<Window>>, H: EventHandler<T> {
+  assert_ne!(fps, 0, "frame rate must be non-zero");
   let mut window_state = WindowState::new(source.into());
+  window_state.frame_limiter = Some(FrameLimiter::new(Duration::from_secs(1) / fps));
+  run_window_state(event_loop, window_state, handler)
+}
+
+fn run_window_state<T, H>(event_loop: EventLoop<T>, mut window_state: WindowState, handler: H) -> !
+where H: EventHandler<T> {
   let mut handler = Some(handler);
   event_loop.run(move |event, _, cf| {
     if let Event::LoopDestroyed = event {
@@ -506,9 +698,170 @@ where W: Into<Rc<Window>>, H: EventHandler<T> {
     } else {
       let handler = unwrap_unreachable!(handler.as_mut());
       window_state.handle_event(handler, event, cf);
+      if let Some(control_flow) = handler.control_flow(&window_state) {
+        if !matches!(cf, ControlFlow::ExitWithCode(_)) {
+          *cf = control_flow;
+        };
+      };
+
       if handler.should_exit(&window_state) {
         *cf = ControlFlow::Exit;
       };
     };
   });
 }
+
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[allow(deprecated)]
+  fn keyboard_input(keycode: VirtualKeyCode, scancode: ScanCode, state: ElementState) -> KeyboardInput {
+    KeyboardInput { scancode, state, virtual_keycode: Some(keycode), modifiers: ModifiersState::empty() }
+  }
+
+  fn keyboard_event(keycode: VirtualKeyCode, scancode: ScanCode, state: ElementState) -> Event<'static, ()> {
+    let input = keyboard_input(keycode, scancode, state);
+    let event = WindowEvent::KeyboardInput { device_id: unsafe { DeviceId::dummy() }, input, is_synthetic: false };
+    Event::WindowEvent { window_id: unsafe { WindowId::dummy() }, event }
+  }
+
+  #[test]
+  fn key_action_modifiers() {
+    let mut input_state = InputState::default();
+    input_state.handle_keyboard_input(keyboard_input(VirtualKeyCode::A, 30, ElementState::Pressed));
+    input_state.modifiers_state = ModifiersState::SHIFT;
+    input_state.handle_keyboard_input(keyboard_input(VirtualKeyCode::B, 48, ElementState::Pressed));
+
+    let modifiers = input_state.key_actions().iter()
+      .map(|action| (action.keycode, action.modifiers))
+      .collect::<Vec<_>>();
+    assert_eq!(modifiers, [
+      (VirtualKeyCode::A, ModifiersState::empty()),
+      (VirtualKeyCode::B, ModifiersState::SHIFT)
+    ]);
+  }
+
+  #[test]
+  fn keys_pressed() {
+    let mut input_state = InputState::default();
+    input_state.handle_keyboard_input(keyboard_input(VirtualKeyCode::A, 30, ElementState::Pressed));
+    input_state.handle_keyboard_input(keyboard_input(VirtualKeyCode::B, 48, ElementState::Pressed));
+    input_state.handle_keyboard_input(keyboard_input(VirtualKeyCode::A, 30, ElementState::Released));
+    // held keys are reported as repeating rather than pressed
+    input_state.handle_keyboard_input(keyboard_input(VirtualKeyCode::B, 48, ElementState::Pressed));
+
+    assert_eq!(input_state.keys_pressed().collect::<Vec<_>>(), [VirtualKeyCode::A, VirtualKeyCode::B]);
+    assert_eq!(input_state.keys_released().collect::<Vec<_>>(), [VirtualKeyCode::A]);
+
+    input_state.reset();
+    assert_eq!(input_state.keys_pressed().count(), 0);
+  }
+
+  #[test]
+  fn input_state_held() {
+    let input_state = InputState::default()
+      .with_keys_held([VirtualKeyCode::LShift, VirtualKeyCode::W])
+      .with_buttons_held([MouseButton::Right]);
+    assert!(input_state.is_key_held(VirtualKeyCode::LShift));
+    assert!(input_state.is_key_held(VirtualKeyCode::W));
+    assert!(!input_state.is_key_held(VirtualKeyCode::S));
+    assert!(input_state.is_button_held(MouseButton::Right));
+    assert!(!input_state.is_button_held(MouseButton::Left));
+    assert!(input_state.key_actions().is_empty());
+  }
+
+  #[test]
+  fn window_state_headless() {
+    #[derive(Default)]
+    struct Recorder {
+      shift_held: Vec<bool>
+    }
+
+    impl EventHandler for Recorder {
+      fn keyboard_input(&mut self, window_state: &WindowState, _: KeyState, _: Option<VirtualKeyCode>, _: ScanCode) {
+        self.shift_held.push(window_state.input().is_key_held(VirtualKeyCode::LShift));
+      }
+    }
+
+    let input_state = InputState::default().with_keys_held([VirtualKeyCode::LShift]);
+    let mut window_state = WindowState::headless((640, 480), 2.0, input_state);
+    assert_eq!(window_state.window_size(), (640, 480));
+    assert_eq!(window_state.scale_factor(), 2.0);
+    assert!(window_state.input().is_key_held(VirtualKeyCode::LShift));
+
+    let mut handler = Recorder::default();
+    let mut cf = ControlFlow::Poll;
+    window_state.handle_event(&mut handler, keyboard_event(VirtualKeyCode::W, 17, ElementState::Pressed), &mut cf);
+    window_state.handle_event(&mut handler, Event::MainEventsCleared, &mut cf);
+
+    assert_eq!(handler.shift_held, [true]);
+    assert!(window_state.input().is_key_held(VirtualKeyCode::W));
+    assert!(window_state.input().was_key_pressed(VirtualKeyCode::W));
+  }
+
+  #[test]
+  fn consume_input() {
+    let mut input_state = InputState::default();
+    input_state.handle_keyboard_input(keyboard_input(VirtualKeyCode::A, 30, ElementState::Pressed));
+    input_state.handle_keyboard_input(keyboard_input(VirtualKeyCode::B, 48, ElementState::Pressed));
+    input_state.handle_mouse_input(ElementState::Pressed, MouseButton::Left);
+
+    assert!(input_state.consume_key(VirtualKeyCode::A));
+    assert!(!input_state.consume_key(VirtualKeyCode::A));
+    assert!(!input_state.was_key_pressed(VirtualKeyCode::A));
+    assert!(input_state.was_key_pressed(VirtualKeyCode::B));
+    assert!(input_state.is_key_held(VirtualKeyCode::A));
+
+    assert!(!input_state.consume_mouse(MouseButton::Right));
+    assert!(input_state.consume_mouse(MouseButton::Left));
+    assert!(input_state.mouse_actions().is_empty());
+    assert!(input_state.is_button_held(MouseButton::Left));
+
+    // a handler consuming input before `update` hides it from `update` in the same frame
+    #[derive(Default)]
+    struct Modal {
+      seen: Vec<(bool, bool)>
+    }
+
+    impl EventHandler for Modal {
+      fn filter_input(&mut self, input_state: &mut InputState) {
+        input_state.consume_key(VirtualKeyCode::A);
+      }
+
+      fn update(&mut self, window_state: &WindowState) {
+        let input_state = window_state.input();
+        self.seen.push((input_state.was_key_pressed(VirtualKeyCode::A), input_state.was_key_pressed(VirtualKeyCode::B)));
+      }
+    }
+
+    let mut window_state = WindowState::headless((640, 480), 1.0, InputState::default());
+    let mut handler = Modal::default();
+    let mut cf = ControlFlow::Poll;
+    window_state.handle_event(&mut handler, keyboard_event(VirtualKeyCode::A, 30, ElementState::Pressed), &mut cf);
+    window_state.handle_event(&mut handler, keyboard_event(VirtualKeyCode::B, 48, ElementState::Pressed), &mut cf);
+    window_state.handle_event(&mut handler, Event::MainEventsCleared, &mut cf);
+    assert_eq!(handler.seen, [(false, true)]);
+    assert!(window_state.input().is_key_held(VirtualKeyCode::A));
+  }
+
+  #[test]
+  fn ime_preedit() {
+    let mut input_state = InputState::default();
+    input_state.handle_ime(&Ime::Enabled);
+    input_state.handle_ime(&Ime::Preedit("にほ".to_owned(), Some((6, 6))));
+    assert_eq!(input_state.preedit(), "にほ");
+    assert_eq!(input_state.preedit_cursor(), Some((6, 6)));
+
+    // composition persists across frames until it is committed
+    input_state.reset();
+    assert_eq!(input_state.preedit(), "にほ");
+
+    input_state.handle_ime(&Ime::Preedit(String::new(), None));
+    input_state.handle_ime(&Ime::Commit("日本".to_owned()));
+    assert_eq!(input_state.preedit(), "");
+    assert_eq!(input_state.preedit_cursor(), None);
+  }
+}
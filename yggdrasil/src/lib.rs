@@ -7,16 +7,35 @@
 
 use std::io;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Gets the canonicalized path of the executable itself.
+///
+/// Unlike [`root`], this always points at the binary rather than its directory,
+/// and never takes `CARGO_MANIFEST_DIR` into account.
+pub fn exe_path() -> io::Result<PathBuf> {
+  // Though foolish the wanderer may be, no foolishness surpasses that of Microsoft Windows.
+  dunce::canonicalize(env::current_exe()?)
+}
 
 /// Gets the current application root (directory of the executable).
+///
+/// When run through cargo, `CARGO_MANIFEST_DIR` is returned instead,
+/// see [`root_exe_only`] if this is undesirable.
 pub fn root() -> io::Result<PathBuf> {
   if let Some(manifest_dir) = env::var_os("CARGO_MANIFEST_DIR") {
     return Ok(PathBuf::from(manifest_dir));
   };
 
-  // Though foolish the wanderer may be, no foolishness surpasses that of Microsoft Windows.
-  let mut current_exe = dunce::canonicalize(env::current_exe()?)?;
+  root_exe_only()
+}
+
+/// Gets the directory of the executable, even when run through cargo.
+///
+/// Unlike [`root`], this never takes `CARGO_MANIFEST_DIR` into account,
+/// matching the layout the application would see once deployed.
+pub fn root_exe_only() -> io::Result<PathBuf> {
+  let mut current_exe = exe_path()?;
 
   if current_exe.pop() {
     return Ok(current_exe);
@@ -28,6 +47,13 @@ pub fn root() -> io::Result<PathBuf> {
   ))
 }
 
+/// Joins the given path onto the application root (directory of the executable).
+///
+/// Unlike [`reroot`], this leaves the current directory of the process untouched.
+pub fn resolve(relative: impl AsRef<Path>) -> io::Result<PathBuf> {
+  root().map(|root| root.join(relative))
+}
+
 /// Set the current root directory to the application root (directory of the executable).
 pub fn reroot() -> io::Result<()> {
   root().and_then(env::set_current_dir)
@@ -0,0 +1,27 @@
+extern crate yggdrasil;
+
+use std::env;
+use std::path::Path;
+
+#[test]
+fn exe_path() {
+  let exe_path = yggdrasil::exe_path().unwrap();
+  let root = yggdrasil::root_exe_only().unwrap();
+  assert!(exe_path.is_file());
+  assert_eq!(exe_path.parent(), Some(root.as_path()));
+}
+
+#[test]
+fn resolve() {
+  let path = yggdrasil::resolve("assets/config.toml").unwrap();
+  assert!(path.is_absolute());
+  assert!(path.ends_with("assets/config.toml"));
+}
+
+#[test]
+fn root_exe_only() {
+  let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").expect("tests are run through cargo");
+  let root = yggdrasil::root_exe_only().unwrap();
+  assert_ne!(root, Path::new(&manifest_dir));
+  assert_eq!(yggdrasil::root().unwrap(), Path::new(&manifest_dir));
+}